@@ -0,0 +1,129 @@
+/*
+ *  rhai-doc offline search
+ *
+ *  Queries the index in `rhai-doc-search-index.js`, which sets `window.RHAI_DOC_SEARCH_INDEX`.
+ */
+(function () {
+    var MAX_RESULTS = 20;
+
+    function escapeHtml(text) {
+        return text
+            .replace(/&/g, "&amp;")
+            .replace(/</g, "&lt;")
+            .replace(/>/g, "&gt;")
+            .replace(/"/g, "&quot;");
+    }
+
+    function score(entry, terms) {
+        var name = entry.name.toLowerCase();
+        var title = entry.title.toLowerCase();
+        var text = entry.text.toLowerCase();
+        var total = 0;
+
+        for (var i = 0; i < terms.length; i++) {
+            var term = terms[i];
+
+            if (name === term) {
+                total += 100;
+            } else if (name.indexOf(term) === 0) {
+                total += 50;
+            } else if (name.indexOf(term) >= 0) {
+                total += 20;
+            } else if (title.indexOf(term) >= 0) {
+                total += 10;
+            } else if (text.indexOf(term) >= 0) {
+                total += 1;
+            } else {
+                // All terms must match
+                return 0;
+            }
+        }
+
+        return total;
+    }
+
+    function snippet(text, terms) {
+        var lower = text.toLowerCase();
+        var pos = -1;
+
+        for (var i = 0; i < terms.length && pos < 0; i++) {
+            pos = lower.indexOf(terms[i]);
+        }
+
+        var start = Math.max(0, pos - 30);
+        var result = text.substr(start, 100);
+
+        return (start > 0 ? "..." : "") + result + (start + 100 < text.length ? "..." : "");
+    }
+
+    function search(query) {
+        var index = window.RHAI_DOC_SEARCH_INDEX || [];
+        var terms = query.toLowerCase().split(/\s+/).filter(function (t) { return t.length > 0; });
+
+        if (terms.length === 0) return [];
+
+        return index
+            .map(function (entry) { return { entry: entry, score: score(entry, terms) }; })
+            .filter(function (r) { return r.score > 0; })
+            .sort(function (a, b) { return b.score - a.score; })
+            .slice(0, MAX_RESULTS)
+            .map(function (r) { return r.entry; });
+    }
+
+    document.addEventListener("DOMContentLoaded", function () {
+        var input = document.getElementById("search-input");
+        var results = document.getElementById("search-results");
+
+        if (!input || !results) return;
+
+        var root = input.getAttribute("data-root") || "";
+
+        function render() {
+            var query = input.value;
+            var terms = query.toLowerCase().split(/\s+/).filter(function (t) { return t.length > 0; });
+            var found = search(query);
+
+            if (query.trim().length === 0) {
+                results.innerHTML = "";
+                results.classList.remove("open");
+                return;
+            }
+
+            if (found.length === 0) {
+                results.innerHTML = '<li class="empty">No results</li>';
+            } else {
+                results.innerHTML = found.map(function (entry) {
+                    var title = entry.kind !== "page"
+                        ? "<code>" + escapeHtml(entry.title) + "</code>"
+                        : escapeHtml(entry.title);
+
+                    return '<li><a href="' + escapeHtml(root + entry.link) + '">'
+                        + '<span class="search-title">' + title + "</span>"
+                        + (entry.text ? '<span class="search-text">' + escapeHtml(snippet(entry.text, terms)) + "</span>" : "")
+                        + "</a></li>";
+                }).join("");
+            }
+
+            results.classList.add("open");
+        }
+
+        input.addEventListener("input", render);
+        input.addEventListener("focus", render);
+
+        input.addEventListener("keydown", function (event) {
+            if (event.key === "Escape") {
+                input.value = "";
+                render();
+            } else if (event.key === "Enter") {
+                var first = results.querySelector("a");
+                if (first) window.location.href = first.getAttribute("href");
+            }
+        });
+
+        document.addEventListener("click", function (event) {
+            if (event.target !== input && !results.contains(event.target)) {
+                results.classList.remove("open");
+            }
+        });
+    });
+})();
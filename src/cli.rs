@@ -1,4 +1,5 @@
 use clap::{ArgAction, Parser, Subcommand};
+use rhai_doc::OutputFormat;
 use std::path::PathBuf;
 
 pub const RHAI_TOML: &str = "rhai.toml";
@@ -20,6 +21,18 @@ pub struct Cli {
     #[arg(long, short)]
     pub all: bool,
 
+    /// Exit with an error if any warnings are found (e.g. broken links)
+    #[arg(long)]
+    pub deny_warnings: bool,
+
+    /// Exit with an error if any public function has no doc-comment
+    #[arg(long)]
+    pub deny_missing_docs: bool,
+
+    /// Write the documentation coverage report (JSON and SVG badge) into the destination
+    #[arg(long)]
+    pub coverage: bool,
+
     /// Set the configuration file
     #[arg(long, short, value_name = "FILE", default_value = RHAI_TOML)]
     pub config: PathBuf,
@@ -32,6 +45,15 @@ pub struct Cli {
     /// Set the destination for the documentation output
     #[arg(long = "dest", short = 'D', value_name = "DIR", default_value = "dist")]
     pub destination: PathBuf,
+    /// Set the output formats, comma-separated (html, json, markdown)
+    #[arg(
+        long,
+        short,
+        value_name = "FORMAT",
+        value_delimiter = ',',
+        default_value = "html"
+    )]
+    pub format: Vec<OutputFormat>,
 
     /// Sub-commands
     #[command(subcommand)]
@@ -47,4 +69,24 @@ pub enum RhaiDocCommand {
         #[arg(long, short, value_name = "FILE", default_value = RHAI_TOML)]
         config: String,
     },
+    /// Generates the documentation, then regenerates it whenever a source file changes
+    Watch {
+        /// Sets the delay (in milliseconds) to wait for further changes before regenerating
+        #[arg(long, value_name = "MS", default_value_t = 500)]
+        delay: u64,
+    },
+    /// Generates the documentation and serves it over HTTP, reloading pages when it is regenerated
+    Serve {
+        /// Sets the address to listen on
+        #[arg(long, value_name = "ADDR", default_value = "127.0.0.1")]
+        host: String,
+        /// Sets the port to listen on
+        #[arg(long, value_name = "PORT", default_value_t = 3000)]
+        port: u16,
+        /// Sets the delay (in milliseconds) to wait for further changes before regenerating
+        #[arg(long, value_name = "MS", default_value_t = 500)]
+        delay: u64,
+    },
+    /// Runs the code examples in doc-comments and MarkDown pages
+    Test,
 }
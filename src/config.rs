@@ -1,11 +1,21 @@
 use crate::data::*;
-use serde::{Deserialize, Serialize};
+use crate::error::RhaiDocError;
+use rhai::Engine;
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::fs::File;
+use std::io::prelude::Read;
+use std::path::Path;
+use std::str::FromStr;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Config {
     pub version: String,
     pub name: Option<String>,
     pub color: Option<Rgb>,
+    #[serde(default)]
+    pub palette: PaletteConfig,
     pub icon: Option<String>,
     pub stylesheet: Option<String>,
     pub code_theme: Option<String>,
@@ -13,12 +23,139 @@ pub struct Config {
     pub root: Option<String>,
     pub index: Option<String>,
     pub extension: Option<String>,
+    pub templates: Option<String>,
     #[serde(default)]
     pub links: Vec<Link>,
     pub google_analytics: Option<String>,
+    #[serde(default)]
+    pub offline: bool,
+    pub source_url: Option<String>,
+    pub source_rev: Option<String>,
+    #[serde(default)]
+    pub metadata: Vec<String>,
+    #[serde(default)]
+    pub engine: EngineConfig,
+}
+
+impl Config {
+    /// Load a configuration from a TOML file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RhaiDocError> {
+        let mut file = File::open(path)?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        Ok(toml::from_str(&content)?)
+    }
+}
+
+/// Settings of the [`Engine`] used to compile scripts, which should match the host application's.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct EngineConfig {
+    #[serde(default)]
+    pub disabled_symbols: Vec<String>,
+    #[serde(default)]
+    pub custom_operators: Vec<CustomOperator>,
+    #[serde(default)]
+    pub custom_syntax: Vec<CustomSyntax>,
+    pub max_expr_depths: Option<MaxExprDepths>,
+    #[serde(default)]
+    pub strict_variables: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CustomOperator {
+    pub symbol: String,
+    pub precedence: u8,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CustomSyntax {
+    pub segments: Vec<String>,
+    #[serde(default)]
+    pub scope_may_change: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MaxExprDepths {
+    pub expr: usize,
+    pub functions: usize,
+}
+
+impl EngineConfig {
+    /// Create an [`Engine`] with these settings.
+    ///
+    /// Custom syntax is only parsed: evaluating it (e.g. in a code example) is an error.
+    pub fn create_engine(&self) -> Result<Engine, RhaiDocError> {
+        let mut engine = Engine::new();
+
+        for symbol in &self.disabled_symbols {
+            engine.disable_symbol(symbol);
+        }
+
+        for CustomOperator { symbol, precedence } in &self.custom_operators {
+            engine
+                .register_custom_operator(symbol, *precedence)
+                .map_err(|error| {
+                    RhaiDocError::Internal(format!(
+                        "Invalid custom operator `{}`: {}",
+                        symbol, error
+                    ))
+                })?;
+        }
+
+        for syntax in &self.custom_syntax {
+            let message = format!(
+                "custom syntax `{}` cannot be run by rhai-doc (mark the example `no_run`)",
+                syntax.segments.join(" ")
+            );
+
+            engine
+                .register_custom_syntax(&syntax.segments, syntax.scope_may_change, move |_, _| {
+                    Err(message.as_str().into())
+                })
+                .map_err(|error| {
+                    RhaiDocError::Internal(format!(
+                        "Invalid custom syntax `{}`: {}",
+                        syntax.segments.join(" "),
+                        error
+                    ))
+                })?;
+        }
+
+        if let Some(MaxExprDepths { expr, functions }) = self.max_expr_depths {
+            engine.set_max_expr_depths(expr, functions);
+        }
+
+        engine.set_strict_variables(self.strict_variables);
+
+        Ok(engine)
+    }
+}
+
+/// Colors of the light and dark palettes, overriding those derived from the theme color.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PaletteConfig {
+    /// Show a button to switch between light and dark mode.
+    #[serde(default)]
+    pub toggle: bool,
+    #[serde(default)]
+    pub light: PaletteColors,
+    #[serde(default)]
+    pub dark: PaletteColors,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PaletteColors {
+    pub background: Option<Rgb>,
+    pub surface: Option<Rgb>,
+    pub text: Option<Rgb>,
+    pub muted: Option<Rgb>,
+    pub border: Option<Rgb>,
+    pub accent: Option<Rgb>,
+    pub link: Option<Rgb>,
+}
+
+/// An RGB color, written as `[r, g, b]` or as a hex string (e.g. `"#f67702"` or `"#f70"`).
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
 pub struct Rgb(pub u8, pub u8, pub u8);
 
 impl Rgb {
@@ -27,17 +164,80 @@ impl Rgb {
     }
 }
 
-impl ToString for Rgb {
-    fn to_string(&self) -> String {
-        format!("rgb({r}, {g}, {b})", r = self.0, g = self.1, b = self.2)
+impl FromStr for Rgb {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        let error = || format!("invalid color `{}`, expected `#rrggbb` or `#rgb`", s);
+
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(error());
+        }
+
+        let channel = |digits: &str| u8::from_str_radix(digits, 16).map_err(|_| error());
+
+        match hex.len() {
+            6 => Ok(Rgb(
+                channel(&hex[0..2])?,
+                channel(&hex[2..4])?,
+                channel(&hex[4..6])?,
+            )),
+            3 => Ok(Rgb(
+                channel(&hex[0..1])? * 17,
+                channel(&hex[1..2])? * 17,
+                channel(&hex[2..3])? * 17,
+            )),
+            _ => Err(error()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Rgb {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RgbVisitor;
+
+        impl<'de> Visitor<'de> for RgbVisitor {
+            type Value = Rgb;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an `[r, g, b]` array or a `#rrggbb` hex string")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Rgb, E> {
+                value.parse().map_err(E::custom)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Rgb, A::Error> {
+                let mut channel = |index| {
+                    seq.next_element::<u8>()?
+                        .ok_or_else(|| de::Error::invalid_length(index, &self))
+                };
+                let color = Rgb(channel(0)?, channel(1)?, channel(2)?);
+
+                match seq.next_element::<de::IgnoredAny>()? {
+                    Some(_) => Err(de::Error::invalid_length(4, &self)),
+                    None => Ok(color),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(RgbVisitor)
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rgb({r}, {g}, {b})", r = self.0, g = self.1, b = self.2)
     }
 }
 
 pub struct Rgba(pub u8, pub u8, pub u8, pub u8);
 
-impl ToString for Rgba {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for Rgba {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "rgba({r}, {g}, {b}, {a})",
             r = self.0,
             g = self.1,
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Page {
@@ -9,10 +10,17 @@ pub struct Page {
     pub stylesheet: Option<String>,
     pub code_theme: String,
     pub code_lang: String,
+    pub offline: bool,
+    pub theme_toggle: bool,
+    pub description: Option<String>,
+    pub module_doc: Option<String>,
+    pub constants: Option<Vec<Constant>>,
     pub functions: Option<Vec<Function>>,
     pub markdown: Option<String>,
+    pub source_url: Option<String>,
     pub external_links: Vec<Link>,
     pub page_links: Vec<super::LinkInfo>,
+    pub nav: Navigation,
     pub script_links: Vec<super::LinkInfo>,
     pub google_analytics: Option<String>,
 }
@@ -20,8 +28,42 @@ pub struct Page {
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
 pub struct Function {
     pub id: String,
+    pub name: String,
+    pub params: Vec<String>,
+    pub access: String,
     pub definition: String,
     pub is_private: bool,
+    pub comments: String,
+    pub markdown: String,
+    pub param_docs: Vec<ParamDoc>,
+    pub returns: Option<String>,
+    pub returns_markdown: Option<String>,
+    /// Line of the function definition in its script, if script-defined.
+    pub line: Option<usize>,
+    /// Link to the line of the function definition in the source page of its script.
+    pub source_link: Option<String>,
+    /// Link to the line of the function definition in an external repository browser.
+    pub source_url: Option<String>,
+}
+
+/// Documentation of a function parameter, from a `# Parameters` section or `@param` tag.
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct ParamDoc {
+    pub name: String,
+    pub description: String,
+    pub markdown: String,
+}
+
+/// A documented top-level constant or variable in a script.
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct Constant {
+    pub id: String,
+    pub name: String,
+    pub value: Option<String>,
+    pub definition: String,
+    pub is_const: bool,
+    pub exported: bool,
+    pub comments: String,
     pub markdown: String,
 }
 
@@ -30,3 +72,101 @@ pub struct Link {
     pub name: String,
     pub link: String,
 }
+
+/// Navigation of the pages, as a tree of sections mirroring the pages directory.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Navigation {
+    pub tree: Vec<NavItem>,
+    /// Sections containing the current page, then the page itself.
+    pub breadcrumbs: Vec<NavItem>,
+    pub prev: Option<NavItem>,
+    pub next: Option<NavItem>,
+}
+
+/// A page, or a section of pages, in the navigation.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct NavItem {
+    pub name: String,
+    /// Link to the page, or to the `_index.md` page of a section.
+    pub link: Option<String>,
+    pub active: bool,
+    /// Whether the section contains the current page.
+    pub open: bool,
+    pub children: Vec<NavItem>,
+    /// Directory of a section.
+    #[serde(skip)]
+    pub dir: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct SearchEntry {
+    pub kind: String,
+    pub name: String,
+    pub title: String,
+    pub text: String,
+    pub link: String,
+}
+
+/// The full documentation model, written as JSON by the `json` output format.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Documentation {
+    pub title: String,
+    pub root: Option<String>,
+    pub pages: Vec<MarkdownPage>,
+    pub scripts: Vec<Script>,
+    pub external_links: Vec<Link>,
+    pub page_links: Vec<super::LinkInfo>,
+    pub script_links: Vec<super::LinkInfo>,
+    pub dependencies: Vec<Dependencies>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MarkdownPage {
+    pub name: String,
+    pub path: PathBuf,
+    pub link: String,
+    pub description: Option<String>,
+    pub hidden: bool,
+    pub markdown: String,
+    pub html: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Script {
+    pub name: String,
+    pub path: PathBuf,
+    pub link: String,
+    pub doc: Option<String>,
+    pub doc_html: Option<String>,
+    pub constants: Vec<Constant>,
+    pub functions: Vec<Function>,
+}
+
+/// Documentation coverage of the public functions in all scripts.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Coverage {
+    pub documented: usize,
+    pub total: usize,
+    pub percent: f64,
+    pub scripts: Vec<ScriptCoverage>,
+}
+
+/// Documentation coverage of the public functions in a script.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct ScriptCoverage {
+    pub name: String,
+    pub path: PathBuf,
+    pub documented: usize,
+    pub total: usize,
+    pub percent: f64,
+    pub undocumented: Vec<String>,
+}
+
+/// Scripts imported by, and importing, a script.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Dependencies {
+    pub name: String,
+    pub link: Option<String>,
+    pub imports: Vec<Link>,
+    pub imported_by: Vec<Link>,
+}
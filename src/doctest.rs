@@ -0,0 +1,229 @@
+//! Running the code examples in doc-comments and MarkDown pages.
+
+use crate::generator::{module_doc, script_function};
+use crate::{config, error, source};
+use glob::glob;
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag};
+use rhai::{Engine, AST};
+use std::fs::File;
+use std::io::prelude::Read;
+use std::path::Path;
+
+/// Summary of a run of code examples.
+#[derive(Debug, Clone, Default)]
+pub struct TestSummary {
+    /// Number of code examples that passed.
+    pub passed: usize,
+    /// Number of code examples that failed.
+    pub failed: usize,
+    /// Number of code examples marked `ignore`.
+    pub ignored: usize,
+}
+
+/// A fenced code example.
+#[derive(Debug, Clone, Default)]
+struct Example {
+    code: String,
+    /// Only compile the example, do not run it.
+    no_run: bool,
+    /// The example must fail to compile or run.
+    should_fail: bool,
+    /// Skip the example.
+    ignore: bool,
+}
+
+/// Attributes of code examples, which are not languages.
+const ATTRIBUTES: &[&str] = &["no_run", "should_fail", "should_panic", "ignore"];
+
+/// Split the info string of a fenced code block (e.g. `rhai,no_run`) into tokens.
+fn info_tokens(info: &str) -> impl Iterator<Item = &str> {
+    info.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+}
+
+/// Get the language of a fenced code block, ignoring any attributes of code examples.
+pub(crate) fn code_lang(info: &str) -> Option<&str> {
+    info_tokens(info).find(|token| !ATTRIBUTES.contains(token))
+}
+
+/// Extract the code examples from MarkDown text: fenced code blocks that are untagged or tagged
+/// `rhai`, with optional attributes (e.g. ```` ```rhai,no_run ````).
+fn examples(markdown: &str) -> Vec<Example> {
+    let mut examples = Vec::new();
+    let mut current: Option<Example> = None;
+
+    for event in Parser::new_ext(markdown, Options::all()) {
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info)))
+                if code_lang(&info).is_none_or(|lang| lang == "rhai") =>
+            {
+                let mut example = Example::default();
+
+                for token in info_tokens(&info) {
+                    match token {
+                        "no_run" => example.no_run = true,
+                        "should_fail" | "should_panic" => example.should_fail = true,
+                        "ignore" => example.ignore = true,
+                        _ => (),
+                    }
+                }
+
+                current = Some(example);
+            }
+            Event::Text(text) => {
+                if let Some(ref mut example) = current {
+                    example.code.push_str(&text);
+                }
+            }
+            Event::End(Tag::CodeBlock(..)) => examples.extend(current.take()),
+            _ => (),
+        }
+    }
+
+    examples
+}
+
+/// Run a code example, with the functions of its script (if any) in scope.
+fn run_example(engine: &Engine, functions: Option<&AST>, example: &Example) -> Result<(), String> {
+    let ast = engine
+        .compile(&example.code)
+        .map_err(|error| error.to_string())?;
+
+    if example.no_run {
+        return Ok(());
+    }
+
+    let ast = match functions {
+        Some(functions) => functions.merge(&ast),
+        None => ast,
+    };
+
+    engine.run_ast(&ast).map_err(|error| error.to_string())
+}
+
+/// Test all the code examples in a piece of documentation.
+fn test_doc(
+    engine: &Engine,
+    functions: Option<&AST>,
+    markdown: &str,
+    path: &Path,
+    item: &str,
+    summary: &mut TestSummary,
+    quiet: bool,
+) {
+    for (i, example) in examples(markdown).iter().enumerate() {
+        let name = format!("`{}` {} (example {})", path.to_string_lossy(), item, i + 1);
+
+        if example.ignore {
+            write_log!(!quiet, "test {} ... ignored", name);
+            summary.ignored += 1;
+            continue;
+        }
+
+        let result = match (run_example(engine, functions, example), example.should_fail) {
+            (Ok(_), false) | (Err(_), true) => Ok(()),
+            (Ok(_), true) => Err("expected to fail, but succeeded".to_string()),
+            (Err(error), false) => Err(error),
+        };
+
+        match result {
+            Ok(_) => {
+                write_log!(!quiet, "test {} ... ok", name);
+                summary.passed += 1;
+            }
+            Err(error) => {
+                write_log!(!quiet, "test {} ... FAILED", name);
+                eprintln!("Example failed: {}: {}", name, error);
+                summary.failed += 1;
+            }
+        }
+    }
+}
+
+/// Run the code examples in the doc-comments of all scripts and in all MarkDown pages.
+pub fn run(
+    config: &config::Config,
+    scripts: &Path,
+    pages: &Path,
+    quiet: bool,
+    debug: bool,
+) -> Result<TestSummary, error::RhaiDocError> {
+    let mut engine = config.engine.create_engine()?;
+    let mut summary = TestSummary::default();
+
+    if !debug {
+        engine.on_print(|_| ());
+        engine.on_debug(|_, _, _| ());
+    }
+
+    write_log!(!quiet, "Testing code examples in `{}`...", @scripts);
+
+    for path in glob(&scripts.to_string_lossy())?.flatten() {
+        if !path.is_file() {
+            continue;
+        }
+
+        let mut script = String::new();
+        File::open(&path)?.read_to_string(&mut script)?;
+
+        let ast = match engine.compile(source::hide_declaration_comments(&script)) {
+            Ok(ast) => ast,
+            Err(error) => {
+                eprintln!(
+                    "Error compiling `{file}`: {error}",
+                    file = path.to_string_lossy(),
+                    error = error
+                );
+                summary.failed += 1;
+                continue;
+            }
+        };
+
+        let functions = ast.clone_functions_only();
+
+        if let Some(doc) = module_doc(&ast) {
+            test_doc(
+                &engine,
+                Some(&functions),
+                &doc,
+                &path,
+                "module documentation",
+                &mut summary,
+                quiet,
+            );
+        }
+
+        for function in ast.iter_functions() {
+            let function = script_function(&function);
+
+            test_doc(
+                &engine,
+                Some(&functions),
+                &function.comments,
+                &path,
+                &format!("`{}`", function.definition),
+                &mut summary,
+                quiet,
+            );
+        }
+    }
+
+    write_log!(!quiet, "Testing code examples in `{}`...", @pages);
+
+    for path in glob(&pages.to_string_lossy())?.flatten() {
+        let mut markdown = String::new();
+        File::open(&path)?.read_to_string(&mut markdown)?;
+
+        test_doc(&engine, None, &markdown, &path, "page", &mut summary, quiet);
+    }
+
+    write_log!(
+        !quiet,
+        "Test result: {} passed; {} failed; {} ignored",
+        summary.passed,
+        summary.failed,
+        summary.ignored
+    );
+
+    Ok(summary)
+}
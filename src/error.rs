@@ -5,6 +5,10 @@ use std::fmt;
 pub enum RhaiDocError {
     Internal(String),
     Icon(String),
+    Compile(usize),
+    Warnings(usize),
+    MissingDocs(usize),
+    Doctests(usize),
 }
 
 impl fmt::Display for RhaiDocError {
@@ -12,10 +16,20 @@ impl fmt::Display for RhaiDocError {
         match self {
             RhaiDocError::Internal(message) => write!(f, "{}", message),
             RhaiDocError::Icon(message) => write!(f, "Icon Error: {}", message),
+            RhaiDocError::Compile(count) => write!(f, "{} script(s) failed to compile", count),
+            RhaiDocError::Warnings(count) => {
+                write!(f, "{} warning(s) found with warnings denied", count)
+            }
+            RhaiDocError::MissingDocs(count) => {
+                write!(f, "{} public function(s) without documentation", count)
+            }
+            RhaiDocError::Doctests(count) => write!(f, "{} code example(s) failed", count),
         }
     }
 }
 
+impl std::error::Error for RhaiDocError {}
+
 macro_rules! impl_error {
     { $ERROR_TYPE:ty } => {
         impl From<$ERROR_TYPE> for RhaiDocError {
@@ -30,7 +44,9 @@ impl_error!(handlebars::TemplateError);
 impl_error!(glob::PatternError);
 impl_error!(std::str::Utf8Error);
 impl_error!(std::boxed::Box<rhai::EvalAltResult>);
+impl_error!(rhai::ParseError);
 impl_error!(handlebars::RenderError);
 impl_error!(std::io::Error);
 impl_error!(toml::de::Error);
+impl_error!(serde_json::Error);
 impl_error!(std::path::StripPrefixError);
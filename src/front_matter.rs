@@ -0,0 +1,308 @@
+//! Front matter of MarkDown pages, in TOML (between `+++` lines) or YAML (between `---` lines).
+
+use serde::Deserialize;
+
+/// Front matter of a MarkDown page.
+///
+/// Other keys (e.g. `date` or `tags`, used by other site generators) are ignored.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct FrontMatter {
+    /// Title of the page, instead of its first heading.
+    pub title: Option<String>,
+    /// Position of the page in the navigation; pages without an order come last.
+    pub order: Option<i64>,
+    /// Generate the page, but leave it out of the navigation, the search index and `SUMMARY.md`.
+    #[serde(default)]
+    pub hidden: bool,
+    /// Output file name, without the `.html` extension.
+    pub slug: Option<String>,
+    /// Description of the page, in its `<meta name="description">` tag.
+    pub description: Option<String>,
+    /// Handlebars template for the page, instead of `markdown`.
+    pub template: Option<String>,
+}
+
+/// Split the front matter, if any, from a MarkDown page.
+///
+/// Returns the front matter (the default if there is none) and the rest of the page.
+/// Invalid front matter is left in the page, so that its text is not lost.
+pub fn parse(markdown: &str) -> (Result<FrontMatter, String>, &str) {
+    let first_line = markdown.lines().next().unwrap_or_default().trim_end();
+
+    let delimiter = match first_line {
+        "+++" | "---" => first_line,
+        _ => return (Ok(FrontMatter::default()), markdown),
+    };
+
+    let start = markdown.find('\n').map_or(markdown.len(), |n| n + 1);
+    let mut end = start;
+
+    for line in markdown[start..].split_inclusive('\n') {
+        if line.trim_end() == delimiter {
+            let front_matter = &markdown[start..end];
+            let body = &markdown[end + line.len()..];
+
+            let result = if delimiter == "+++" {
+                toml::from_str(front_matter).map_err(|error| error.to_string())
+            } else if front_matter.trim().is_empty() {
+                Ok(FrontMatter::default())
+            } else {
+                parse_yaml(front_matter)
+            };
+
+            return match result {
+                Ok(_) => (result, body),
+                Err(_) => (result, markdown),
+            };
+        }
+        end += line.len();
+    }
+
+    // Without a closing delimiter, there is no front matter (e.g. `---` is a horizontal rule)
+    (Ok(FrontMatter::default()), markdown)
+}
+
+/// Parse YAML front matter, which is limited to one `key: value` line per key.
+///
+/// Values are plain, `"double-quoted"` or `'single-quoted'` scalars; other keys may have any value
+/// (e.g. a list of tags on the following lines), as they are skipped.
+fn parse_yaml(yaml: &str) -> Result<FrontMatter, String> {
+    let mut front_matter = FrontMatter::default();
+    let mut key = "";
+
+    for (n, line) in yaml.lines().enumerate() {
+        let error = |message: String| format!("line {}: {}", n + 1, message);
+        let line = line.trim_end();
+
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        // Indented lines and list items belong to the value of the previous key
+        if line.starts_with(char::is_whitespace) || line.starts_with('-') {
+            if FIELDS.contains(&key) {
+                return Err(error(format!("unsupported value of `{}`", key)));
+            }
+            continue;
+        }
+
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| error("expected `key: value`".to_string()))?;
+
+        key = name.trim();
+
+        if !FIELDS.contains(&key) {
+            continue;
+        }
+
+        let value = yaml_scalar(value).map_err(error)?;
+
+        let value = match value {
+            Some(value) => value,
+            None => continue,
+        };
+
+        match key {
+            "title" => front_matter.title = Some(value),
+            "order" => {
+                front_matter.order = Some(
+                    value
+                        .parse()
+                        .map_err(|_| error(format!("invalid `order`: `{}`", value)))?,
+                )
+            }
+            "hidden" => {
+                front_matter.hidden = match value.as_str() {
+                    "true" | "True" | "TRUE" => true,
+                    "false" | "False" | "FALSE" => false,
+                    _ => return Err(error(format!("invalid `hidden`: `{}`", value))),
+                }
+            }
+            "slug" => front_matter.slug = Some(value),
+            "description" => front_matter.description = Some(value),
+            "template" => front_matter.template = Some(value),
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(front_matter)
+}
+
+/// Keys of the front matter.
+const FIELDS: &[&str] = &[
+    "title",
+    "order",
+    "hidden",
+    "slug",
+    "description",
+    "template",
+];
+
+/// Read a YAML scalar on a single line; `null`, `~` and an empty value are `None`.
+fn yaml_scalar(value: &str) -> Result<Option<String>, String> {
+    let value = value.trim();
+
+    if let Some(rest) = value.strip_prefix('"') {
+        let mut text = String::new();
+        let mut chars = rest.chars();
+
+        loop {
+            match chars.next() {
+                Some('"') => break,
+                Some('\\') => match chars.next() {
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some(c @ ('"' | '\\' | '/')) => text.push(c),
+                    _ => return Err("invalid escape in double-quoted value".to_string()),
+                },
+                Some(c) => text.push(c),
+                None => return Err("unterminated double-quoted value".to_string()),
+            }
+        }
+
+        return match chars.as_str().trim() {
+            rest if rest.is_empty() || rest.starts_with('#') => Ok(Some(text)),
+            _ => Err("unexpected text after double-quoted value".to_string()),
+        };
+    }
+
+    if let Some(rest) = value.strip_prefix('\'') {
+        let mut text = String::new();
+        let mut rest = rest;
+
+        loop {
+            let n = rest
+                .find('\'')
+                .ok_or_else(|| "unterminated single-quoted value".to_string())?;
+            text.push_str(&rest[..n]);
+            rest = &rest[n + 1..];
+
+            // A quote is escaped by doubling it
+            match rest.strip_prefix('\'') {
+                Some(after) => {
+                    text.push('\'');
+                    rest = after;
+                }
+                None => break,
+            }
+        }
+
+        return match rest.trim() {
+            rest if rest.is_empty() || rest.starts_with('#') => Ok(Some(text)),
+            _ => Err("unexpected text after single-quoted value".to_string()),
+        };
+    }
+
+    // A comment in a plain value starts after a space
+    let value = match value.find(" #") {
+        Some(n) => value[..n].trim_end(),
+        None => value,
+    };
+
+    match value {
+        "" | "~" | "null" | "Null" | "NULL" => Ok(None),
+        _ if value.starts_with(['|', '>', '[', '{', '&', '*', '!']) => {
+            Err(format!("unsupported value `{}`", value))
+        }
+        _ => Ok(Some(value.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_toml() {
+        let (front_matter, body) =
+            parse("+++\ntitle = \"Start\"\norder = 2\nhidden = true\n+++\n# Body\n");
+        let front_matter = front_matter.unwrap();
+
+        assert_eq!(front_matter.title.as_deref(), Some("Start"));
+        assert_eq!(front_matter.order, Some(2));
+        assert!(front_matter.hidden);
+        assert_eq!(body, "# Body\n");
+    }
+
+    #[test]
+    fn parses_yaml() {
+        let (front_matter, body) =
+            parse("---\r\nslug: start\r\ndescription: How to start.\r\n---\r\nText.");
+        let front_matter = front_matter.unwrap();
+
+        assert_eq!(front_matter.slug.as_deref(), Some("start"));
+        assert_eq!(front_matter.description.as_deref(), Some("How to start."));
+        assert!(!front_matter.hidden);
+        assert_eq!(body, "Text.");
+
+        let (front_matter, body) = parse("---\n---\nText.");
+        assert!(front_matter.unwrap().title.is_none());
+        assert_eq!(body, "Text.");
+    }
+
+    #[test]
+    fn parses_yaml_scalars() {
+        let (front_matter, _) = parse(concat!(
+            "---\n",
+            "# Comment\n",
+            "title: \"Say \\\"hi\\\"\" # quoted\n",
+            "slug: 'it''s'\n",
+            "description: Plain text#1 # comment\n",
+            "order: -3\n",
+            "hidden: True\n",
+            "template: ~\n",
+            "---\n"
+        ));
+        let front_matter = front_matter.unwrap();
+
+        assert_eq!(front_matter.title.as_deref(), Some("Say \"hi\""));
+        assert_eq!(front_matter.slug.as_deref(), Some("it's"));
+        assert_eq!(front_matter.description.as_deref(), Some("Plain text#1"));
+        assert_eq!(front_matter.order, Some(-3));
+        assert!(front_matter.hidden);
+        assert!(front_matter.template.is_none());
+    }
+
+    #[test]
+    fn horizontal_rule_is_not_front_matter() {
+        let markdown = "---\n\nText after a rule.\n";
+        let (front_matter, body) = parse(markdown);
+
+        assert!(front_matter.unwrap().title.is_none());
+        assert_eq!(body, markdown);
+
+        let markdown = "# Title\n\n---\n\ntitle: not front matter\n\n---\n";
+        assert_eq!(parse(markdown).1, markdown);
+    }
+
+    #[test]
+    fn ignores_unknown_keys() {
+        let (front_matter, body) =
+            parse("+++\ndate = 2024-01-01\ntags = [\"a\", \"b\"]\ntitle = \"Start\"\n+++\nText.");
+
+        assert_eq!(front_matter.unwrap().title.as_deref(), Some("Start"));
+        assert_eq!(body, "Text.");
+
+        let (front_matter, _) = parse("---\nweight: 10\ntags:\n  - a\ntitle: Start\n---\n");
+        assert_eq!(front_matter.unwrap().title.as_deref(), Some("Start"));
+    }
+
+    #[test]
+    fn reports_invalid_front_matter() {
+        let markdown = "+++\norder = \"first\"\n+++\nText.";
+        let (front_matter, body) = parse(markdown);
+
+        assert!(front_matter.is_err());
+        assert_eq!(body, markdown);
+
+        let (front_matter, _) = parse("---\norder: first\n---\n");
+        assert!(front_matter.is_err());
+
+        let (front_matter, _) = parse("---\ntitle: \"Start\n---\n");
+        assert!(front_matter.is_err());
+
+        let (front_matter, _) = parse("---\ndescription: >\n  Folded.\n---\n");
+        assert!(front_matter.is_err());
+    }
+}
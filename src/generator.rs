@@ -0,0 +1,2407 @@
+use crate::doctest::{self, TestSummary};
+use crate::front_matter::{self, FrontMatter};
+use crate::palette::Palette;
+use crate::{config, data, error, graph, highlight, metadata, nav, params, source, LinkInfo};
+use glob::glob;
+use handlebars::{handlebars_helper, Handlebars};
+use pulldown_cmark::escape::escape_html;
+use pulldown_cmark::{html, BrokenLink, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag};
+use rhai::{FnAccess, ScriptFnMetadata, AST};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::File;
+use std::io::prelude::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+
+/// Output format of the documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputFormat {
+    /// HTML documentation site.
+    Html,
+    /// The full documentation model as JSON (`rhai-doc.json`).
+    Json,
+    /// MarkDown files, one per script, plus a `SUMMARY.md` fragment (e.g. for `mdBook`).
+    Markdown,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "html" => Ok(Self::Html),
+            "json" => Ok(Self::Json),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => Err(format!("unknown output format `{}`", s)),
+        }
+    }
+}
+
+/// Documentation generator.
+///
+/// The pages and destination directories are relative to the source directory,
+/// unless they are absolute paths.
+#[derive(Debug, Clone)]
+pub struct DocGenerator {
+    config: config::Config,
+    source: PathBuf,
+    pages: PathBuf,
+    destination: PathBuf,
+    formats: Vec<OutputFormat>,
+    all: bool,
+    deny_warnings: bool,
+    deny_missing_docs: bool,
+    coverage_report: bool,
+    quiet: bool,
+    debug: bool,
+}
+
+/// A problem found in the documentation that does not stop its generation.
+#[derive(Debug, Clone)]
+pub struct Warning {
+    /// Script or MarkDown page containing the problem.
+    pub path: PathBuf,
+    /// Function whose documentation contains the problem, if any.
+    pub function: Option<String>,
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.function {
+            Some(ref function) => write!(
+                f,
+                "`{}` in `{}`: {}",
+                function,
+                self.path.to_string_lossy(),
+                self.message
+            ),
+            None => write!(f, "`{}`: {}", self.path.to_string_lossy(), self.message),
+        }
+    }
+}
+
+/// A script that failed to compile, which is left out of the documentation.
+#[derive(Debug, Clone)]
+pub struct CompileError {
+    /// Script that failed to compile.
+    pub path: PathBuf,
+    /// Line of the error, if known.
+    pub line: Option<usize>,
+    /// Column of the error, if known.
+    pub column: Option<usize>,
+    /// Description of the error.
+    pub message: String,
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}", self.path.to_string_lossy())?;
+
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+
+            if let Some(column) = self.column {
+                write!(f, ":{}", column)?;
+            }
+        }
+
+        write!(f, "`: {}", self.message)
+    }
+}
+
+/// A MarkDown page to render as HTML.
+struct HtmlPage {
+    name: String,
+    dest_path: PathBuf,
+    html: String,
+    /// Index of the page in the page links, unless it is hidden.
+    link_index: Option<usize>,
+    template: String,
+    description: Option<String>,
+}
+
+/// Summary of a documentation generation run.
+#[derive(Debug, Clone, Default)]
+pub struct Summary {
+    /// Destination directory of the generated documentation.
+    pub destination: PathBuf,
+    /// Number of MarkDown pages written.
+    pub pages: usize,
+    /// Number of Rhai scripts documented.
+    pub scripts: usize,
+    /// Number of functions documented.
+    pub functions: usize,
+    /// Scripts that failed to compile.
+    pub compile_errors: Vec<CompileError>,
+    /// Problems found in the documentation.
+    pub warnings: Vec<Warning>,
+    /// Documentation coverage of public functions.
+    pub coverage: data::Coverage,
+}
+
+impl DocGenerator {
+    /// Create a new [`DocGenerator`] with the specified configuration.
+    ///
+    /// By default, scripts are read from the current directory, pages from `pages` and the output
+    /// written to `dist`.
+    pub fn new(config: config::Config) -> Self {
+        Self {
+            config,
+            source: PathBuf::from("."),
+            pages: PathBuf::from("pages"),
+            destination: PathBuf::from("dist"),
+            formats: vec![OutputFormat::Html],
+            all: false,
+            deny_warnings: false,
+            deny_missing_docs: false,
+            coverage_report: false,
+            quiet: false,
+            debug: false,
+        }
+    }
+    /// Set the Rhai scripts directory.
+    pub fn source(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.source = dir.into();
+        self
+    }
+    /// Set the directory where MarkDown pages are located.
+    pub fn pages(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.pages = dir.into();
+        self
+    }
+    /// Set the destination directory for the documentation output.
+    pub fn destination(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.destination = dir.into();
+        self
+    }
+    /// Set the output formats (default HTML only).
+    pub fn formats(mut self, formats: impl IntoIterator<Item = OutputFormat>) -> Self {
+        self.formats = formats.into_iter().collect();
+        self
+    }
+    /// Generate documentation for all functions, including private ones.
+    pub fn all(mut self, all: bool) -> Self {
+        self.all = all;
+        self
+    }
+    /// Fail with an error if any warnings are found (e.g. broken links).
+    ///
+    /// All output is still written.
+    pub fn deny_warnings(mut self, deny_warnings: bool) -> Self {
+        self.deny_warnings = deny_warnings;
+        self
+    }
+    /// Fail with an error if any public function has no doc-comment.
+    ///
+    /// All output is still written.
+    pub fn deny_missing_docs(mut self, deny_missing_docs: bool) -> Self {
+        self.deny_missing_docs = deny_missing_docs;
+        self
+    }
+    /// Write the documentation coverage report (`coverage.json`) and badge (`coverage.svg`)
+    /// into the destination directory.
+    pub fn coverage_report(mut self, coverage_report: bool) -> Self {
+        self.coverage_report = coverage_report;
+        self
+    }
+    /// Suppress all logging output.
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+    /// Output debug logging.
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.debug = debug;
+        self
+    }
+    /// Get the configuration.
+    pub fn config(&self) -> &config::Config {
+        &self.config
+    }
+
+    /// Glob pattern of the Rhai scripts.
+    fn scripts_pattern(&self) -> PathBuf {
+        let mut pattern = self.source.clone();
+        pattern.push("**");
+        pattern.push("*.rhai");
+
+        if let Some(extension) = &self.config.extension {
+            pattern.set_extension(extension.strip_prefix('.').unwrap_or(extension));
+        }
+
+        pattern
+    }
+
+    /// Glob pattern of the MarkDown pages.
+    fn pages_pattern(&self) -> PathBuf {
+        let mut pattern = self.source.clone();
+        pattern.push(&self.pages);
+        pattern.push("**");
+        pattern.push("*.md");
+        pattern
+    }
+
+    /// Run the code examples in the doc-comments of all scripts and in all MarkDown pages.
+    ///
+    /// Nothing is written to the destination directory.
+    pub fn test(&self) -> Result<TestSummary, error::RhaiDocError> {
+        let summary = doctest::run(
+            &self.config,
+            &self.scripts_pattern(),
+            &self.pages_pattern(),
+            self.quiet,
+            self.debug,
+        )?;
+
+        if summary.failed > 0 {
+            Err(error::RhaiDocError::Doctests(summary.failed))
+        } else {
+            Ok(summary)
+        }
+    }
+
+    /// Generate the documentation.
+    ///
+    /// Fails if any script does not compile, or if there are warnings or missing docs when
+    /// [`deny_warnings`][Self::deny_warnings] or [`deny_missing_docs`][Self::deny_missing_docs]
+    /// is set. Warnings are not printed, but returned in the [`Summary`].
+    pub fn generate(&self) -> Result<Summary, error::RhaiDocError> {
+        let summary = self.build()?;
+        self.check(&summary)?;
+        Ok(summary)
+    }
+
+    /// Generate the documentation, only recording compile errors, warnings and missing docs in
+    /// the [`Summary`].
+    ///
+    /// Use [`check`][Self::check] afterwards to fail in the same way as
+    /// [`generate`][Self::generate].
+    pub fn build(&self) -> Result<Summary, error::RhaiDocError> {
+        let config = &self.config;
+        let quiet = self.quiet;
+        let debug = self.debug;
+        let skip_private = !self.all;
+        let write_html = self.formats.contains(&OutputFormat::Html);
+        let write_markdown = self.formats.contains(&OutputFormat::Markdown);
+        let source = self.source.clone();
+
+        write_log!(debug, "{:#?}", config);
+
+        let path_glob_source = self.scripts_pattern();
+
+        write_log!(!quiet, "Script files pattern: `{}`", @path_glob_source);
+
+        // Paths returned by `glob` have `.` components removed
+        let pages_dir = source
+            .join(&self.pages)
+            .components()
+            .filter(|c| c != &Component::CurDir)
+            .collect::<PathBuf>();
+        let index_file = config.index.as_ref().map(|index| pages_dir.join(index));
+
+        let path_pages = self.pages_pattern();
+
+        write_log!(!quiet, "MarkDown pages: `{}`", @path_pages);
+
+        let mut destination = source.clone();
+        destination.push(&self.destination);
+        std::fs::create_dir_all(&destination)?;
+
+        write_log!(!quiet, "Destination directory: `{}`", @destination);
+
+        let mut summary = Summary {
+            destination: destination.clone(),
+            ..Default::default()
+        };
+
+        let mut page_links = Vec::new();
+        let mut script_links = Vec::new();
+        let mut search_index = Vec::new();
+        let mut summary_md = Vec::new();
+        let mut script_constants = BTreeMap::new();
+        let mut script_function_lines = HashMap::new();
+        let mut handlebars = Handlebars::new();
+
+        let options = Options::all();
+        let code_lang = config.code_lang.as_deref().unwrap_or("rhai");
+        let engine = config.engine.create_engine()?;
+
+        // Links to scripts in an external repository browser, with `{path}` and `{line}` left
+        let source_url = config.source_url.as_ref().map(|template| {
+            let rev = match config.source_rev {
+                Some(ref rev) => rev.clone(),
+                None if template.contains("{rev}") => git_revision(&source).unwrap_or_else(|| {
+                    eprintln!(
+                        "Cannot read the git revision of `{}`, using `HEAD` in source links",
+                        source.to_string_lossy()
+                    );
+                    "HEAD".to_string()
+                }),
+                None => String::new(),
+            };
+            template.replace("{rev}", &rev)
+        });
+
+        // `{path}` is relative to the root of the repository, which may be above the scripts
+        let source_prefix = source_url
+            .as_ref()
+            .filter(|template| template.contains("{path}"))
+            .and_then(|_| git_prefix(&source))
+            .unwrap_or_default();
+
+        let mut pages: Vec<HtmlPage> = Vec::new();
+
+        let mut documentation = data::Documentation {
+            title: config.name.clone().unwrap_or_default(),
+            root: config.root.clone(),
+            pages: Vec::new(),
+            scripts: Vec::new(),
+            external_links: config.links.clone(),
+            page_links: Vec::new(),
+            script_links: Vec::new(),
+            dependencies: Vec::new(),
+        };
+
+        handlebars.register_escape_fn(handlebars::no_escape);
+        handlebars.register_helper("highlight", Box::new(highlight_helper));
+        handlebars.register_template_string(
+            "page",
+            std::str::from_utf8(include_bytes!("../assets/page.html.hbs"))?,
+        )?;
+        handlebars.register_partial(
+            "fn-block",
+            std::str::from_utf8(include_bytes!("../assets/fn-block.html.hbs"))?,
+        )?;
+
+        if let Some(ref templates) = config.templates {
+            let mut dir = source.clone();
+            dir.push(templates);
+
+            write_log!(!quiet, "Custom templates: `{}`", @dir);
+
+            register_templates(&mut handlebars, &dir, quiet)?;
+        }
+
+        write_log!(!quiet, "Registered handlebars templates.");
+
+        //
+        //  WRITE FILES
+        //
+        let (icon, stylesheet_filename) = if write_html {
+            write_styles(config, &destination)?;
+            let icon = write_icon(config, &source, &destination)?;
+
+            if config.offline {
+                write_highlight(config, &destination)?;
+            }
+
+            let stylesheet = if let Some(ref stylesheet) = config.stylesheet {
+                let mut css = source.clone();
+                css.push(stylesheet);
+
+                if css.is_file() {
+                    write_log!(!quiet, "Custom stylesheet: `{}`", @css);
+
+                    let mut ss_source = source.clone();
+                    ss_source.push(&css);
+                    let mut ss_dest = destination.clone();
+                    let filename = css.file_name().unwrap().to_string_lossy().into_owned();
+                    ss_dest.push(&filename);
+
+                    let mut file = match File::open(&ss_source) {
+                        Ok(f) => f,
+                        Err(error) => {
+                            eprintln!(
+                                "Cannot load stylesheet `{file}`: {error}",
+                                file = ss_source.to_string_lossy(),
+                                error = error
+                            );
+                            return Err(error.into());
+                        }
+                    };
+                    let mut content = Vec::<u8>::new();
+                    file.read_to_end(&mut content)?;
+                    let mut file = File::create(&ss_dest)?;
+                    file.write_all(&content)?;
+                    Some(filename)
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+
+            write_log!(!quiet, "Written styles and icon.");
+
+            (icon, stylesheet)
+        } else {
+            (String::new(), None)
+        };
+
+        //
+        //  PAGE LINKS
+        //
+        write_log!(!quiet, "Scanning for MarkDown pages from `{}`...", @path_pages);
+
+        let mut files_list = glob(&path_pages.to_string_lossy())?
+            .flatten()
+            .collect::<Vec<_>>();
+        files_list.sort();
+
+        // Read all pages first, to sort them by the `order` in their front matter
+        let mut markdown_pages = Vec::new();
+
+        for src_path in files_list {
+            let mut content = String::new();
+            File::open(&src_path)?.read_to_string(&mut content)?;
+
+            let (front_matter, markdown) = front_matter::parse(&content);
+
+            let front_matter = front_matter.unwrap_or_else(|error| {
+                summary.warnings.push(Warning {
+                    path: src_path.clone(),
+                    function: None,
+                    message: format!("invalid front matter: {}", error),
+                });
+                FrontMatter::default()
+            });
+
+            let markdown = markdown.to_string();
+            markdown_pages.push((src_path, front_matter, markdown));
+        }
+
+        // Pages with an order come first; other pages stay sorted by path
+        markdown_pages
+            .sort_by_key(|(_, front_matter, _)| (front_matter.order.is_none(), front_matter.order));
+
+        // Move the home page to the front
+        let mut has_index = false;
+
+        if let Some(ref index_file) = index_file {
+            if let Some(n) = markdown_pages.iter().position(|(p, ..)| p == index_file) {
+                let page = markdown_pages.remove(n);
+                markdown_pages.insert(0, page);
+                has_index = true;
+            }
+        }
+
+        // Source of each output file, to detect pages overwriting each other
+        let mut output_files = HashMap::new();
+
+        for (src_path, front_matter, markdown_string) in markdown_pages {
+            write_log!(!quiet, "> Generating HTML from MarkDown page `{}`...", @src_path);
+
+            let mut file_path = html_from_pathbuf(&src_path, &source);
+
+            if index_file.as_ref() == Some(&src_path) {
+                file_path = PathBuf::from("index.html");
+            } else if let Some(ref slug) = front_matter.slug {
+                // A slug only replaces the file name, so it must not lead out of the directory
+                if slug.is_empty() || slug.contains(['/', '\\']) || slug.contains("..") {
+                    summary.warnings.push(Warning {
+                        path: src_path.clone(),
+                        function: None,
+                        message: format!("invalid slug `{}`, using the file name", slug),
+                    });
+                } else {
+                    file_path.set_file_name(format!("{}.html", slug));
+                }
+            }
+
+            if let Some(other) = output_files.insert(file_path.clone(), src_path.clone()) {
+                summary.warnings.push(Warning {
+                    path: src_path.clone(),
+                    function: None,
+                    message: format!(
+                        "output file `{}` is also generated from `{}`",
+                        file_path.to_string_lossy(),
+                        other.to_string_lossy()
+                    ),
+                });
+            }
+
+            let dest_path = destination.join(&file_path);
+
+            for reference in broken_links(&markdown_string, "", options) {
+                summary.warnings.push(Warning {
+                    path: src_path.clone(),
+                    function: None,
+                    message: format!("unresolved link `[{}]`", reference),
+                });
+            }
+
+            let html_output = doc_to_html(&markdown_string, "", code_lang, options);
+
+            let name = front_matter
+                .title
+                .clone()
+                .or_else(|| markdown_title(&markdown_string, options))
+                .unwrap_or_else(|| {
+                    // The page of a section is named after its directory
+                    let (path, kind) = match src_path.parent() {
+                        Some(dir)
+                            if dir != pages_dir
+                                && src_path
+                                    .file_stem()
+                                    .is_some_and(|stem| stem == nav::SECTION_INDEX) =>
+                        {
+                            (dir, "directory")
+                        }
+                        _ => (src_path.as_path(), "file"),
+                    };
+
+                    summary.warnings.push(Warning {
+                        path: src_path.clone(),
+                        function: None,
+                        message: format!(
+                            "page has no `# Title` heading or front-matter `title`, \
+                             using the {} name",
+                            kind
+                        ),
+                    });
+                    path.file_stem()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .into_owned()
+                });
+
+            let template = match front_matter.template {
+                Some(ref template) if handlebars.has_template(template) => template.clone(),
+                Some(ref template) => {
+                    summary.warnings.push(Warning {
+                        path: src_path.clone(),
+                        function: None,
+                        message: format!("unknown template `{}`", template),
+                    });
+                    template_name(&handlebars, "markdown").to_string()
+                }
+                None => template_name(&handlebars, "markdown").to_string(),
+            };
+
+            let link = link_from_pathbuf(&file_path);
+
+            if write_markdown {
+                let mut md_path = file_path.clone();
+                md_path.set_extension("md");
+                let md_link = link_from_pathbuf(&md_path);
+
+                write_log!(!quiet, "  -> MarkDown page `{}`...", md_link);
+
+                write_file(&destination.join(&md_path), markdown_string.as_bytes())?;
+
+                if !front_matter.hidden {
+                    summary_md.push(format!("- [{}]({})", name, md_link));
+                }
+            }
+
+            documentation.pages.push(data::MarkdownPage {
+                name: name.clone(),
+                path: src_path.clone(),
+                link: link.clone(),
+                description: front_matter.description.clone(),
+                hidden: front_matter.hidden,
+                markdown: markdown_string.clone(),
+                html: html_output.clone(),
+            });
+
+            // Hidden pages are generated, but not linked from other pages
+            let link_index = if front_matter.hidden {
+                None
+            } else {
+                search_index.push(data::SearchEntry {
+                    kind: "page".to_string(),
+                    name: name.clone(),
+                    title: name.clone(),
+                    text: markdown_to_text(&markdown_string, options),
+                    link: link.clone(),
+                });
+
+                page_links.push(LinkInfo {
+                    path: src_path,
+                    active: false,
+                    name: name.clone(),
+                    link,
+                    sub_links: Default::default(),
+                    ast: None,
+                    doc: None,
+                });
+                Some(page_links.len() - 1)
+            };
+
+            pages.push(HtmlPage {
+                name,
+                dest_path,
+                html: html_output,
+                link_index,
+                template,
+                description: front_matter.description,
+            });
+        }
+
+        //
+        //  SCRIPT LINKS
+        //
+        write_log!(!quiet, "Scanning for Rhai scripts from `{}`...", @path_glob_source);
+
+        for entry in glob(&path_glob_source.to_string_lossy())? {
+            match entry {
+                Ok(path) if path.is_file() => {
+                    write_log!(!quiet, "> Found Rhai script `{}`", @path);
+
+                    let mut name = path.clone();
+                    name.set_extension("");
+
+                    let name = match name.strip_prefix(&source) {
+                        Ok(name) => name,
+                        Err(_) => &name,
+                    }
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+
+                    let mut script = String::new();
+                    File::open(&path)?.read_to_string(&mut script)?;
+
+                    let ast = match engine.compile(source::hide_declaration_comments(&script)) {
+                        Ok(ast) => ast,
+                        Err(error) => {
+                            let rhai::ParseError(error_type, pos) = error;
+                            summary.compile_errors.push(CompileError {
+                                path: path.clone(),
+                                line: pos.line(),
+                                column: pos.position(),
+                                message: error_type.to_string(),
+                            });
+                            continue;
+                        }
+                    };
+
+                    let constants = source::declarations(&script)
+                        .into_iter()
+                        .filter(|decl| !decl.comments.is_empty())
+                        .map(|decl| script_constant(&decl))
+                        .collect::<Vec<_>>();
+
+                    if constants.is_empty()
+                        && ast
+                            .iter_functions()
+                            .filter(|f| !skip_private || f.access != FnAccess::Private)
+                            .count()
+                            == 0
+                    {
+                        write_log!(!quiet, "  ... which contains no functions. Skipped.");
+                        continue;
+                    }
+
+                    script_constants.insert(path.clone(), constants);
+                    script_function_lines.insert(path.clone(), source::function_lines(&script));
+
+                    let doc_path = html_from_pathbuf(&path, &source);
+
+                    let link = doc_path
+                        .components()
+                        .map(|s| s.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/");
+
+                    write_log!(!quiet, "  -> {}", link);
+
+                    // Use the title of the module documentation, if any, as the display name
+                    let doc = module_doc(&ast);
+                    let name = doc
+                        .as_deref()
+                        .and_then(|doc| markdown_title(doc, options))
+                        .unwrap_or(name);
+
+                    script_links.push(LinkInfo {
+                        path: path.clone(),
+                        name,
+                        active: false,
+                        link,
+                        sub_links: Default::default(),
+                        ast: Some(ast),
+                        doc,
+                    })
+                }
+                Ok(_) => {}
+                Err(error) => eprintln!(
+                    "Error loading script files `{pattern}`: {error}",
+                    pattern = path_glob_source.to_string_lossy(),
+                    error = error
+                ),
+            }
+        }
+
+        //
+        //  API LINKS
+        //
+        let mut api_pages = BTreeMap::new();
+
+        for file in &config.metadata {
+            let mut path = source.clone();
+            path.push(file);
+
+            write_log!(!quiet, "> Loading functions metadata `{}`", @path);
+
+            let mut json = String::new();
+
+            if let Err(error) = File::open(&path).and_then(|mut f| f.read_to_string(&mut json)) {
+                eprintln!(
+                    "Cannot load functions metadata `{file}`: {error}",
+                    file = path.to_string_lossy(),
+                    error = error
+                );
+                return Err(error.into());
+            }
+
+            let module: metadata::ModuleMetadata = serde_json::from_str(&json)?;
+            let functions = api_functions(&module, skip_private);
+
+            if functions.is_empty() {
+                write_log!(!quiet, "  ... which contains no functions. Skipped.");
+                continue;
+            }
+
+            // The metadata file may be anywhere, so the page is named after it under `api/`
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let link = format!("api/{}.html", stem);
+
+            if let Some(other) = script_links.iter().find(|info| info.link == link) {
+                summary.warnings.push(Warning {
+                    path: path.clone(),
+                    function: None,
+                    message: format!(
+                        "output file `{}` is also generated from `{}`",
+                        link,
+                        other.path.to_string_lossy()
+                    ),
+                });
+            }
+
+            write_log!(!quiet, "  -> {}", link);
+
+            let doc = Some(module.doc).filter(|doc| !doc.trim().is_empty());
+            let name = doc
+                .as_deref()
+                .and_then(|doc| markdown_title(doc, options))
+                .unwrap_or_else(|| stem.clone());
+
+            api_pages.insert(path.clone(), functions);
+
+            script_links.push(LinkInfo {
+                path,
+                name,
+                active: false,
+                link,
+                sub_links: Default::default(),
+                ast: None,
+                doc,
+            })
+        }
+
+        //
+        //  IMPORTS
+        //
+        let mut import_graph = graph::ImportGraph::default();
+        let script_ext = config
+            .extension
+            .as_ref()
+            .map_or("rhai", |ext| ext.strip_prefix('.').unwrap_or(ext));
+        let canonical_source = source.canonicalize()?;
+
+        for info in &script_links {
+            let ast = match info.ast {
+                Some(ref ast) => ast,
+                None => continue,
+            };
+
+            let module = info.link.strip_suffix(".html").unwrap_or(&info.link);
+            let from = import_graph.node(module, Some(&info.link));
+
+            for import in graph::imports(ast) {
+                let to = match graph::resolve(&import, &info.path, &source, script_ext) {
+                    Some(path) => match script_links.iter().find(|l| {
+                        l.ast.is_some() && l.path.canonicalize().ok() == Some(path.clone())
+                    }) {
+                        Some(target) => {
+                            let module = target.link.strip_suffix(".html").unwrap_or(&target.link);
+                            import_graph.node(module, Some(&target.link))
+                        }
+                        None => {
+                            let mut name = path
+                                .strip_prefix(&canonical_source)
+                                .unwrap_or(&path)
+                                .to_path_buf();
+                            name.set_extension("");
+                            import_graph.node(&link_from_pathbuf(&name), None)
+                        }
+                    },
+                    None => import_graph.node(&import, None),
+                };
+
+                write_log!(
+                    debug,
+                    "Import `{}` in `{}` -> `{}`",
+                    import,
+                    module,
+                    import_graph.nodes[to].name
+                );
+
+                import_graph.add_import(from, to);
+            }
+        }
+
+        let has_imports = !import_graph.edges.is_empty();
+
+        if has_imports && write_html {
+            page_links.push(LinkInfo {
+                path: PathBuf::from("dependencies.html"),
+                active: false,
+                name: "Dependencies".to_string(),
+                link: "dependencies.html".to_string(),
+                sub_links: Default::default(),
+                ast: None,
+                doc: None,
+            });
+        }
+
+        //
+        //  PAGES
+        //
+        if write_html {
+            write_log!(!quiet, "Writing HTML pages...");
+        } else {
+            pages.clear();
+        }
+
+        for page in pages {
+            let HtmlPage {
+                name,
+                dest_path,
+                html: markdown,
+                link_index,
+                template,
+                description,
+            } = page;
+
+            write_log!(!quiet, "  -> HTML page `{}`...", @dest_path);
+
+            // Templates are not escaped, so escape the description for its attribute
+            let description = description.map(|text| {
+                let mut escaped = String::new();
+                escape_html(&mut escaped, &text).unwrap();
+                escaped
+            });
+
+            let mut links_clone = page_links.clone();
+            if let Some(i) = link_index {
+                links_clone[i].active = true;
+            }
+
+            let root = if let Some(ref r) = config.root {
+                r.clone()
+            } else {
+                match dest_path.strip_prefix(&destination)?.ancestors().count() {
+                    0..=1 => String::new(),
+                    levels => "../".repeat(levels - 2),
+                }
+            };
+
+            let page = data::Page {
+                title: config.name.clone().unwrap_or_default(),
+                name,
+                root,
+                icon: icon.clone(),
+                stylesheet: stylesheet_filename.clone(),
+                code_theme: config
+                    .code_theme
+                    .clone()
+                    .unwrap_or_else(|| "default".to_string()),
+                code_lang: code_lang.to_string(),
+                offline: config.offline,
+                theme_toggle: config.palette.toggle,
+                description,
+                module_doc: None,
+                constants: None,
+                functions: None,
+                markdown: Some(markdown),
+                source_url: None,
+                external_links: config.links.clone(),
+                nav: nav::navigation(&links_clone, &pages_dir),
+                page_links: links_clone,
+                script_links: script_links.clone(),
+                google_analytics: config.google_analytics.clone(),
+            };
+            if let Some(dir) = dest_path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let mut file = File::create(&dest_path)?;
+
+            file.write_all(handlebars.render(&template, &page)?.as_bytes())?;
+            summary.pages += 1;
+        }
+
+        if write_html && !has_index {
+            let mut dest_path = destination.clone();
+            dest_path.push("index.html");
+
+            write_log!(!quiet, "  -> index page `{}`...", @dest_path);
+
+            let page = data::Page {
+                title: config.name.clone().unwrap_or_default(),
+                name: "index.html".to_string(),
+                root: config.root.clone().unwrap_or_default(),
+                icon: icon.clone(),
+                stylesheet: stylesheet_filename.clone(),
+                code_theme: config
+                    .code_theme
+                    .clone()
+                    .unwrap_or_else(|| "default".to_string()),
+                code_lang: code_lang.to_string(),
+                offline: config.offline,
+                theme_toggle: config.palette.toggle,
+                description: None,
+                module_doc: None,
+                constants: None,
+                functions: None,
+                markdown: None,
+                source_url: None,
+                external_links: config.links.clone(),
+                page_links: page_links.clone(),
+                nav: nav::navigation(&page_links, &pages_dir),
+                script_links: script_links.clone(),
+                google_analytics: config.google_analytics.clone(),
+            };
+            if let Some(dir) = dest_path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let mut file = File::create(&dest_path)?;
+
+            file.write_all(
+                handlebars
+                    .render(template_name(&handlebars, "index"), &page)?
+                    .as_bytes(),
+            )?;
+        }
+
+        //
+        //  DEPENDENCIES
+        //
+        if has_imports {
+            let color = config.color.unwrap_or(config::Rgb(246, 119, 2));
+            let svg = import_graph.to_svg(&color.to_string());
+
+            write_log!(!quiet, "Writing import graph...");
+
+            write_file(
+                &destination.join("dependencies.dot"),
+                import_graph.to_dot().as_bytes(),
+            )?;
+            write_file(&destination.join("dependencies.svg"), svg.as_bytes())?;
+
+            documentation.dependencies = import_graph.dependencies();
+
+            if write_html {
+                let dest_path = destination.join("dependencies.html");
+
+                write_log!(!quiet, "  -> dependencies page `{}`...", @dest_path);
+
+                let mut links_clone = page_links.clone();
+                links_clone.last_mut().unwrap().active = true;
+
+                let markdown = dependencies_to_markdown(&documentation.dependencies);
+                let mut html_output = String::new();
+                html::push_html(&mut html_output, Parser::new_ext(&markdown, options));
+                let html_output = html_output.replacen(
+                    "<!-- graph -->",
+                    &format!("<div class=\"import-graph\">{}</div>", svg),
+                    1,
+                );
+
+                let page = data::Page {
+                    title: config.name.clone().unwrap_or_default(),
+                    name: "dependencies.html".to_string(),
+                    root: config.root.clone().unwrap_or_default(),
+                    icon: icon.clone(),
+                    stylesheet: stylesheet_filename.clone(),
+                    code_theme: config
+                        .code_theme
+                        .clone()
+                        .unwrap_or_else(|| "default".to_string()),
+                    code_lang: code_lang.to_string(),
+                    offline: config.offline,
+                    theme_toggle: config.palette.toggle,
+                    description: None,
+                    module_doc: None,
+                    constants: None,
+                    functions: None,
+                    markdown: Some(html_output),
+                    source_url: None,
+                    external_links: config.links.clone(),
+                    nav: nav::navigation(&links_clone, &pages_dir),
+                    page_links: links_clone,
+                    script_links: script_links.clone(),
+                    google_analytics: config.google_analytics.clone(),
+                };
+
+                write_file(
+                    &dest_path,
+                    handlebars
+                        .render(template_name(&handlebars, "dependencies"), &page)?
+                        .as_bytes(),
+                )?;
+            }
+        }
+
+        //
+        //  SCRIPTS
+        //
+        write_log!(!quiet, "Documenting Rhai scripts...");
+
+        // Functions in all scripts and API pages, sorted by name and number of parameters
+        let all_functions = script_links
+            .iter()
+            .map(|LinkInfo { ast, path, .. }| match ast {
+                Some(ast) => {
+                    let lines: &HashMap<_, _> = &script_function_lines[path];
+
+                    sorted_functions(ast, skip_private)
+                        .iter()
+                        .map(|f| data::Function {
+                            line: lines.get(&(f.name.to_string(), f.params.len())).copied(),
+                            ..script_function(f)
+                        })
+                        .collect()
+                }
+                None => api_pages.remove(path).unwrap_or_default(),
+            })
+            .collect::<Vec<Vec<data::Function>>>();
+
+        // Functions in all scripts, for cross-script links
+        let link_targets = script_links
+            .iter()
+            .zip(all_functions.iter())
+            .map(|(info, functions)| {
+                let page = info
+                    .link
+                    .strip_suffix(".html")
+                    .unwrap_or(info.link.as_str())
+                    .to_string();
+                // API pages are linked to by the name of their metadata file
+                let module = match info.ast {
+                    Some(_) => page.clone(),
+                    None => page.strip_prefix("api/").unwrap_or(&page).to_string(),
+                };
+                let constants = script_constants.get(&info.path).into_iter().flatten();
+                let items = dedup_by_name(functions)
+                    .map(|f| (f.name.clone(), f.id.clone()))
+                    .chain(constants.map(|c| (c.name.clone(), c.id.clone())))
+                    .collect();
+                LinkTarget {
+                    module,
+                    page,
+                    items,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for i in 0..script_links.len() {
+            let LinkInfo {
+                path,
+                ast,
+                link,
+                doc,
+                ..
+            } = &script_links[i];
+
+            let mut new_path = destination.clone();
+            let file_name = PathBuf::from(link);
+            new_path.push(&file_name);
+
+            write_log!(!quiet, "> `{}`...", @path);
+
+            let functions = &all_functions[i];
+            let constants = script_constants.remove(path).unwrap_or_default();
+
+            // Coverage is only for script-defined functions
+            if ast.is_some() {
+                let undocumented = functions
+                    .iter()
+                    .filter(|f| !f.is_private && f.comments.trim().is_empty())
+                    .map(|f| f.definition.clone())
+                    .collect::<Vec<_>>();
+                let total = functions.iter().filter(|f| !f.is_private).count();
+
+                summary.coverage.scripts.push(data::ScriptCoverage {
+                    name: link_targets[i].module.clone(),
+                    path: path.clone(),
+                    documented: total - undocumented.len(),
+                    total,
+                    percent: coverage_percent(total - undocumented.len(), total),
+                    undocumented,
+                });
+            }
+
+            let mut links_clone = script_links.clone();
+            links_clone[i].active = true;
+            links_clone[i].sub_links = constants
+                .iter()
+                .map(|c| data::Link {
+                    name: c.name.clone(),
+                    link: c.id.clone(),
+                })
+                .chain(functions.iter().map(|f| data::Link {
+                    name: f.definition.replacen("fn ", "", 1),
+                    link: f.id.clone(),
+                }))
+                .collect();
+
+            let relative_root = match new_path.strip_prefix(&destination)?.ancestors().count() {
+                0..=1 => String::new(),
+                levels => "../".repeat(levels - 2),
+            };
+            let root = config.root.clone().unwrap_or_else(|| relative_root.clone());
+
+            let mut page = data::Page {
+                title: config.name.clone().unwrap_or_default(),
+                name: file_name.to_string_lossy().to_string(),
+                root,
+                icon: icon.clone(),
+                stylesheet: stylesheet_filename.clone(),
+                code_theme: config
+                    .code_theme
+                    .clone()
+                    .unwrap_or_else(|| "default".to_string()),
+                code_lang: code_lang.to_string(),
+                offline: config.offline,
+                theme_toggle: config.palette.toggle,
+                description: None,
+                module_doc: None,
+                constants: None,
+                functions: Some(Vec::new()),
+                markdown: None,
+                source_url: None,
+                external_links: config.links.clone(),
+                page_links: page_links.clone(),
+                nav: nav::navigation(&page_links, &pages_dir),
+                script_links: links_clone,
+                google_analytics: config.google_analytics.clone(),
+            };
+
+            let local_links = dedup_by_name(functions)
+                .map(|f| (&f.name, &f.id))
+                .chain(constants.iter().map(|c| (&c.name, &c.id)))
+                .map(|(name, id)| format!("[`{}`]: #{}\n", name, id))
+                .collect::<Vec<_>>()
+                .join("");
+
+            let fn_links = format!(
+                "{}{}",
+                local_links,
+                qualified_fn_links(&link_targets, i, &page.root, "html")
+            );
+            let fn_links = fn_links.trim();
+
+            let md_fn_links = format!(
+                "{}{}",
+                local_links,
+                qualified_fn_links(&link_targets, i, &relative_root, "md")
+            );
+            let md_fn_links = md_fn_links.trim();
+
+            // Highlighted copy of the script, for links to function definitions
+            let source_file = match ast {
+                Some(_) if write_html => Some(source_from_pathbuf(path, &source)),
+                _ => None,
+            };
+            let source_link = source_file
+                .as_ref()
+                .map(|file| format!("{}{}", page.root, link_from_pathbuf(file)));
+
+            let script_url = source_url.as_ref().filter(|_| ast.is_some()).map(|url| {
+                let path = link_from_pathbuf(path.strip_prefix(&source).unwrap_or(path));
+                url.replace("{path}", &format!("{}{}", source_prefix, path))
+            });
+            page.source_url = script_url.as_ref().map(|url| url.replace("{line}", "1"));
+
+            let functions = functions
+                .iter()
+                .cloned()
+                .map(|function| {
+                    write_log!(debug, "    -> {}...", function.definition);
+
+                    search_index.push(data::SearchEntry {
+                        kind: "fn".to_string(),
+                        name: function.name.clone(),
+                        title: function.definition.clone(),
+                        text: markdown_to_text(&function.comments, options),
+                        link: format!("{}#{}", link, function.id),
+                    });
+
+                    for reference in broken_links(&function.comments, fn_links, options) {
+                        summary.warnings.push(Warning {
+                            path: path.clone(),
+                            function: Some(function.definition.clone()),
+                            message: format!("unresolved link `[{}]`", reference),
+                        });
+                    }
+
+                    let doc = params::parse(&function.comments);
+
+                    let param_docs = match doc.params {
+                        Some(documented) => {
+                            let names = function
+                                .params
+                                .iter()
+                                .map(|p| p.split(':').next().unwrap_or_default().trim())
+                                .collect::<Vec<_>>();
+
+                            for (name, _) in documented
+                                .iter()
+                                .filter(|(n, _)| !names.contains(&n.as_str()))
+                            {
+                                summary.warnings.push(Warning {
+                                    path: path.clone(),
+                                    function: Some(function.definition.clone()),
+                                    message: format!("documents unknown parameter `{}`", name),
+                                });
+                            }
+
+                            function
+                                .params
+                                .iter()
+                                .zip(names)
+                                .map(|(param, name)| {
+                                    let description = documented
+                                        .iter()
+                                        .find(|(n, _)| n == name)
+                                        .map(|(_, d)| d.clone())
+                                        .unwrap_or_else(|| {
+                                            summary.warnings.push(Warning {
+                                                path: path.clone(),
+                                                function: Some(function.definition.clone()),
+                                                message: format!(
+                                                    "parameter `{}` is not documented",
+                                                    name
+                                                ),
+                                            });
+                                            String::new()
+                                        });
+                                    let markdown = inline_html(&doc_to_html(
+                                        &description,
+                                        fn_links,
+                                        code_lang,
+                                        options,
+                                    ));
+
+                                    data::ParamDoc {
+                                        name: param.clone(),
+                                        description,
+                                        markdown,
+                                    }
+                                })
+                                .collect()
+                        }
+                        None => Vec::new(),
+                    };
+
+                    data::Function {
+                        markdown: doc_to_html(&doc.text, fn_links, code_lang, options),
+                        param_docs,
+                        returns_markdown: doc.returns.as_deref().map(|returns| {
+                            inline_html(&doc_to_html(returns, fn_links, code_lang, options))
+                        }),
+                        returns: doc.returns,
+                        source_link: source_link
+                            .as_ref()
+                            .zip(function.line)
+                            .map(|(link, line)| format!("{}#L{}", link, line)),
+                        source_url: script_url
+                            .as_ref()
+                            .zip(function.line)
+                            .map(|(url, line)| url.replace("{line}", &line.to_string())),
+                        ..function
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let constants = constants
+                .into_iter()
+                .map(|constant| {
+                    write_log!(debug, "    -> {}...", constant.definition);
+
+                    search_index.push(data::SearchEntry {
+                        kind: "const".to_string(),
+                        name: constant.name.clone(),
+                        title: constant.definition.clone(),
+                        text: markdown_to_text(&constant.comments, options),
+                        link: format!("{}#{}", link, constant.id),
+                    });
+
+                    for reference in broken_links(&constant.comments, fn_links, options) {
+                        summary.warnings.push(Warning {
+                            path: path.clone(),
+                            function: Some(constant.definition.clone()),
+                            message: format!("unresolved link `[{}]`", reference),
+                        });
+                    }
+
+                    data::Constant {
+                        markdown: doc_to_html(&constant.comments, fn_links, code_lang, options),
+                        ..constant
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            if ast.is_some() {
+                summary.scripts += 1;
+            }
+            summary.functions += functions.len();
+
+            if let Some(ref doc) = doc {
+                for reference in broken_links(doc, fn_links, options) {
+                    summary.warnings.push(Warning {
+                        path: path.clone(),
+                        function: None,
+                        message: format!("unresolved link `[{}]`", reference),
+                    });
+                }
+            }
+
+            let doc_html = doc
+                .as_deref()
+                .map(|doc| doc_to_html(doc, fn_links, code_lang, options));
+
+            let LinkInfo {
+                name,
+                path,
+                link,
+                sub_links,
+                ..
+            } = page.script_links[i].clone();
+
+            documentation.scripts.push(data::Script {
+                name,
+                path,
+                link,
+                doc: doc.clone(),
+                doc_html: doc_html.clone(),
+                constants: constants.clone(),
+                functions: functions.clone(),
+            });
+            documentation.script_links.push(LinkInfo {
+                active: false,
+                sub_links,
+                ..script_links[i].clone()
+            });
+
+            if write_markdown {
+                let mut md_path = file_name.clone();
+                md_path.set_extension("md");
+                let md_link = link_from_pathbuf(&md_path);
+                let script_name = &page.script_links[i].name;
+
+                write_log!(!quiet, "  -> MarkDown page `{}`...", md_link);
+
+                let content = script_to_markdown(
+                    script_name,
+                    doc.as_deref(),
+                    &constants,
+                    &functions,
+                    md_fn_links,
+                );
+                write_file(&destination.join(&md_path), content.as_bytes())?;
+                summary_md.push(format!("- [`{}`]({})", script_name, md_link));
+            }
+
+            page.module_doc = doc_html;
+            page.constants = Some(constants).filter(|c| !c.is_empty());
+            page.functions = Some(functions);
+
+            if write_html {
+                write_log!(!quiet, "  -> HTML page `{}`...", @new_path);
+
+                if let Some(dir) = new_path.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                let mut file = File::create(&new_path)?;
+
+                file.write_all(
+                    handlebars
+                        .render(template_name(&handlebars, "script"), &page)?
+                        .as_bytes(),
+                )?;
+            }
+
+            if let Some(source_file) = source_file {
+                let LinkInfo { path, link, .. } = &script_links[i];
+                let dest_path = destination.join(&source_file);
+
+                write_log!(!quiet, "  -> source page `{}`...", @dest_path);
+
+                let mut script = String::new();
+                File::open(path)?.read_to_string(&mut script)?;
+
+                let root = match config.root {
+                    Some(ref root) => root.clone(),
+                    None => "../".repeat(source_file.components().count() - 1),
+                };
+                let script_name = link_from_pathbuf(path.strip_prefix(&source).unwrap_or(path));
+
+                page.name = link_from_pathbuf(&source_file);
+                page.markdown = Some(source_to_html(
+                    &script_name,
+                    &script,
+                    &format!("{}{}", root, link),
+                ));
+                page.root = root;
+                page.module_doc = None;
+                page.constants = None;
+                page.functions = None;
+                page.script_links[i].sub_links.clear();
+
+                write_file(
+                    &dest_path,
+                    handlebars
+                        .render(template_name(&handlebars, "source"), &page)?
+                        .as_bytes(),
+                )?;
+            }
+        }
+
+        //
+        //  COVERAGE
+        //
+        let coverage = &mut summary.coverage;
+        coverage.total = coverage.scripts.iter().map(|s| s.total).sum();
+        coverage.documented = coverage.scripts.iter().map(|s| s.documented).sum();
+        coverage.percent = coverage_percent(coverage.documented, coverage.total);
+
+        write_log!(!quiet, "Documentation coverage:");
+
+        for script in &coverage.scripts {
+            write_log!(
+                !quiet,
+                "  {}: {}/{} ({:.1}%)",
+                script.name,
+                script.documented,
+                script.total,
+                script.percent
+            );
+            for function in &script.undocumented {
+                write_log!(!quiet, "    - missing: `{}`", function);
+            }
+        }
+
+        write_log!(
+            !quiet,
+            "  Total: {}/{} ({:.1}%)",
+            coverage.documented,
+            coverage.total,
+            coverage.percent
+        );
+
+        if self.coverage_report {
+            write_log!(!quiet, "Writing coverage report...");
+
+            let file = File::create(destination.join("coverage.json"))?;
+            serde_json::to_writer_pretty(file, coverage)?;
+
+            write_file(
+                &destination.join("coverage.svg"),
+                coverage_badge(coverage.percent).as_bytes(),
+            )?;
+        }
+
+        //
+        //  SEARCH INDEX
+        //
+        if write_html {
+            write_log!(!quiet, "Writing search index...");
+
+            write_search_index(&search_index, &destination)?;
+        }
+
+        //
+        //  SUMMARY.md
+        //
+        if write_markdown {
+            let mut summary_path = destination.clone();
+            summary_path.push("SUMMARY.md");
+
+            write_log!(!quiet, "Writing MarkDown summary `{}`...", @summary_path);
+
+            summary_md.push(String::new());
+            write_file(&summary_path, summary_md.join("\n").as_bytes())?;
+        }
+
+        //
+        //  JSON
+        //
+        if self.formats.contains(&OutputFormat::Json) {
+            let mut json_path = destination.clone();
+            json_path.push("rhai-doc.json");
+
+            write_log!(!quiet, "Writing documentation model `{}`...", @json_path);
+
+            documentation.page_links = page_links;
+
+            let file = File::create(&json_path)?;
+            serde_json::to_writer_pretty(file, &documentation)?;
+        }
+
+        write_log!(
+            !quiet,
+            "Done - documentation generated under `{}`",
+            @destination
+        );
+
+        Ok(summary)
+    }
+
+    /// Check the [`Summary`] of a documentation build for scripts that failed to compile, and for
+    /// warnings and missing docs if they are denied.
+    ///
+    /// Nothing is printed: the undocumented functions are listed in `summary.coverage`.
+    pub fn check(&self, summary: &Summary) -> Result<(), error::RhaiDocError> {
+        if !summary.compile_errors.is_empty() {
+            return Err(error::RhaiDocError::Compile(summary.compile_errors.len()));
+        }
+
+        if self.deny_warnings && !summary.warnings.is_empty() {
+            return Err(error::RhaiDocError::Warnings(summary.warnings.len()));
+        }
+
+        let missing = summary.coverage.total - summary.coverage.documented;
+
+        if self.deny_missing_docs && missing > 0 {
+            return Err(error::RhaiDocError::MissingDocs(missing));
+        }
+
+        Ok(())
+    }
+}
+
+/// Register all Handlebars templates (`*.hbs`) under a directory.
+///
+/// Templates are named after their path relative to the directory, without the `.hbs` and `.html`
+/// extensions (e.g. `partials/footer.html.hbs` becomes `partials/footer`), so `page.html.hbs` and
+/// `fn-block.html.hbs` replace the built-in templates of the same names.
+fn register_templates(
+    handlebars: &mut Handlebars,
+    dir: &Path,
+    quiet: bool,
+) -> Result<(), error::RhaiDocError> {
+    let mut pattern = dir.to_path_buf();
+    pattern.push("**");
+    pattern.push("*.hbs");
+
+    // Paths returned by `glob` have `.` components removed
+    let base = dir
+        .components()
+        .filter(|c| c != &Component::CurDir)
+        .collect::<PathBuf>();
+
+    for path in glob(&pattern.to_string_lossy())?.flatten() {
+        let mut name = path
+            .strip_prefix(&base)
+            .map_or_else(|_| path.clone(), PathBuf::from);
+        name.set_extension("");
+        if name.extension().is_some_and(|ext| ext == "html") {
+            name.set_extension("");
+        }
+        let name = name
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        write_log!(
+            !quiet,
+            "> Template `{}` from `{}`",
+            name,
+            path.to_string_lossy()
+        );
+
+        if let Err(error) = handlebars.register_template_file(&name, &path) {
+            eprintln!(
+                "Cannot load template `{file}`: {error}",
+                file = path.to_string_lossy(),
+                error = error
+            );
+            return Err(error.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Get the name of a template to render, falling back to `page` if it is not registered.
+fn template_name<'a>(handlebars: &Handlebars, name: &'a str) -> &'a str {
+    if handlebars.has_template(name) {
+        name
+    } else {
+        "page"
+    }
+}
+
+fn write_search_index(
+    index: &[data::SearchEntry],
+    destination: &Path,
+) -> Result<(), error::RhaiDocError> {
+    let mut path = destination.to_path_buf();
+    path.push("rhai-doc-search-index.js");
+
+    // The index is a script (not plain JSON) so that it loads under `file://`
+    let mut file = File::create(&path)?;
+    file.write_all(b"window.RHAI_DOC_SEARCH_INDEX = ")?;
+    serde_json::to_writer(&mut file, index)?;
+    file.write_all(b";\n")?;
+
+    let mut path = destination.to_path_buf();
+    path.push("rhai-doc-search.js");
+
+    let mut file = File::create(&path)?;
+    file.write_all(include_bytes!("../assets/search.js"))?;
+
+    Ok(())
+}
+
+/// Write the bundled highlight.js script and code theme stylesheet, for offline output.
+fn write_highlight(config: &config::Config, destination: &Path) -> Result<(), error::RhaiDocError> {
+    let theme = config.code_theme.as_deref().unwrap_or("default");
+
+    let css: &[u8] = match theme {
+        "atom-one-light" => include_bytes!("../assets/highlight/styles/atom-one-light.css"),
+        "default" => include_bytes!("../assets/highlight/styles/default.css"),
+        "github" => include_bytes!("../assets/highlight/styles/github.css"),
+        "tomorrow-night" => include_bytes!("../assets/highlight/styles/tomorrow-night.css"),
+        _ => {
+            eprintln!(
+                "Code theme `{}` is not available offline, using `default`",
+                theme
+            );
+            include_bytes!("../assets/highlight/styles/default.css")
+        }
+    };
+
+    write_file(
+        &destination.join("rhai-doc-highlight.js"),
+        include_bytes!("../assets/highlight/highlight.min.js"),
+    )?;
+    write_file(&destination.join("rhai-doc-highlight.css"), css)?;
+
+    Ok(())
+}
+
+fn write_styles(config: &config::Config, destination: &Path) -> Result<(), error::RhaiDocError> {
+    let mut handlebars = Handlebars::new();
+    let mut styles = destination.to_path_buf();
+
+    handlebars.register_escape_fn(handlebars::no_escape);
+    handlebars.register_template_string(
+        "styles",
+        std::str::from_utf8(include_bytes!("../assets/styles.tpl.css"))?,
+    )?;
+
+    styles.push("rhai-doc-styles.css");
+
+    let color = config.color.unwrap_or(config::Rgb(246, 119, 2));
+    let data = serde_json::json!({
+        "light": Palette::light(color, &config.palette.light),
+        "dark": Palette::dark(color, &config.palette.dark),
+        "offline": config.offline,
+    });
+
+    let mut file = File::create(&styles)?;
+    file.write_all(handlebars.render("styles", &data)?.as_bytes())?;
+
+    Ok(())
+}
+
+fn write_icon(
+    config: &config::Config,
+    source: &Path,
+    destination: &Path,
+) -> Result<String, error::RhaiDocError> {
+    let icon_default = include_bytes!("../assets/logo.svg");
+    let mut source = source.to_path_buf();
+    let mut destination = destination.to_path_buf();
+
+    if let Some(icon) = config.icon.clone() {
+        source.push(&icon);
+
+        let mut file = match File::open(&source) {
+            Ok(f) => f,
+            Err(error) => {
+                eprintln!(
+                    "Cannot load icon `{file}`: {error}",
+                    file = source.to_string_lossy(),
+                    error = error
+                );
+                return Err(error.into());
+            }
+        };
+
+        let mut logo = Vec::new();
+
+        destination.push("logo");
+
+        return match PathBuf::from(&icon).extension() {
+            Some(extension) => {
+                destination.set_extension(extension);
+                file.read_to_end(&mut logo)?;
+
+                let mut new_file = File::create(destination)?;
+                new_file.write_all(&logo)?;
+
+                Ok(format!(
+                    "logo.{extension}",
+                    extension = extension.to_string_lossy()
+                ))
+            }
+            None => Err(error::RhaiDocError::Icon(
+                "Icon must have an extension".into(),
+            )),
+        };
+    }
+
+    destination.push("logo.svg");
+
+    let mut new_file = File::create(destination)?;
+    new_file.write_all(icon_default)?;
+
+    Ok("logo.svg".into())
+}
+
+pub(crate) fn comments_to_string(comments: &[&str]) -> String {
+    comments
+        .iter()
+        .flat_map(|&s| {
+            if s.starts_with("/**") {
+                vec![s]
+            } else {
+                s.split('\n').collect::<Vec<_>>()
+            }
+        })
+        .map(|s| {
+            if s.starts_with("///") || s.starts_with("/**") {
+                if s.ends_with("**/") {
+                    &s[3..s.len() - 3]
+                } else if s.ends_with("*/") {
+                    &s[3..s.len() - 2]
+                } else {
+                    &s[3..]
+                }
+            } else {
+                s
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Get the functions defined in a script, sorted by name and number of parameters.
+fn sorted_functions(ast: &AST, skip_private: bool) -> Vec<ScriptFnMetadata<'_>> {
+    let mut functions = ast
+        .iter_functions()
+        .filter(|f| !skip_private || f.access != FnAccess::Private)
+        .collect::<Vec<_>>();
+
+    functions.sort_by(|a, b| match a.name.partial_cmp(b.name).unwrap() {
+        Ordering::Equal => a.params.len().partial_cmp(&b.params.len()).unwrap(),
+        cmp => cmp,
+    });
+
+    functions
+}
+
+/// Get the module-level documentation of a script, from its inner doc-comments (`//!` lines),
+/// with the comment leaders removed.
+pub(crate) fn module_doc(ast: &AST) -> Option<String> {
+    let doc = ast
+        .doc()
+        .lines()
+        .map(|line| line.strip_prefix("//!").unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if doc.trim().is_empty() {
+        None
+    } else {
+        Some(doc)
+    }
+}
+
+/// Convert a script-defined function into its documentation, without rendering its doc-comments.
+pub(crate) fn script_function(function: &ScriptFnMetadata) -> data::Function {
+    let is_private = function.access == FnAccess::Private;
+
+    data::Function {
+        id: gen_hash_name(function.name, function.params.len()),
+        name: function.name.to_string(),
+        params: function.params.iter().map(|p| p.to_string()).collect(),
+        access: if is_private { "private" } else { "public" }.to_string(),
+        definition: if is_private {
+            function.to_string().replace("private", "private fn")
+        } else {
+            format!("fn {}", function)
+        },
+        is_private,
+        comments: comments_to_string(&function.comments),
+        markdown: String::new(),
+        param_docs: Vec::new(),
+        returns: None,
+        returns_markdown: None,
+        line: None,
+        source_link: None,
+        source_url: None,
+    }
+}
+
+/// Convert a top-level declaration in a script into its documentation, without rendering its
+/// doc-comments.
+fn script_constant(decl: &source::Declaration) -> data::Constant {
+    let keyword = if decl.is_const { "const" } else { "let" };
+    let export = if decl.exported { "export " } else { "" };
+    let comments = decl.comments.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+
+    data::Constant {
+        id: format!("{}-{}", keyword, decl.name),
+        name: decl.name.clone(),
+        value: decl.value.clone(),
+        definition: match decl.value {
+            Some(ref value) => format!("{}{} {} = {}", export, keyword, decl.name, value),
+            None => format!("{}{} {}", export, keyword, decl.name),
+        },
+        is_const: decl.is_const,
+        exported: decl.exported,
+        comments: comments_to_string(&comments),
+        markdown: String::new(),
+    }
+}
+
+/// Convert the functions in a host metadata JSON file into their documentation, sorted by
+/// name and number of parameters, without rendering their doc-comments.
+fn api_functions(module: &metadata::ModuleMetadata, skip_private: bool) -> Vec<data::Function> {
+    let mut functions = module
+        .functions()
+        .into_iter()
+        .filter(|(_, f)| !skip_private || f.access != "private")
+        .collect::<Vec<_>>();
+
+    functions.sort_by(|(a, fa), (b, fb)| match a.cmp(b) {
+        Ordering::Equal => fa.num_params.cmp(&fb.num_params),
+        cmp => cmp,
+    });
+
+    functions
+        .into_iter()
+        .map(|(name, function)| {
+            let is_private = function.access == "private";
+            let namespace = name
+                .strip_suffix(function.name.as_str())
+                .unwrap_or_default();
+            let comments = function
+                .doc_comments
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<_>>();
+
+            data::Function {
+                id: gen_hash_name(&name, function.num_params),
+                params: function
+                    .params
+                    .iter()
+                    .map(|p| p.to_param_string())
+                    .collect(),
+                access: function.access.clone(),
+                definition: format!(
+                    "{}fn {}{}",
+                    if is_private { "private " } else { "" },
+                    namespace,
+                    function.signature
+                ),
+                is_private,
+                comments: comments_to_string(&comments),
+                markdown: String::new(),
+                param_docs: Vec::new(),
+                returns: None,
+                returns_markdown: None,
+                line: None,
+                source_link: None,
+                source_url: None,
+                name,
+            }
+        })
+        .collect()
+}
+
+/// Keep only the first of sorted functions with the same name (i.e. the one with the fewest
+/// parameters), which is the target of a link to that name.
+fn dedup_by_name<'a>(
+    functions: impl IntoIterator<Item = &'a data::Function>,
+) -> impl Iterator<Item = &'a data::Function> {
+    let mut last_name = "";
+
+    functions.into_iter().filter(move |f| {
+        if f.name != last_name {
+            last_name = &f.name;
+            true
+        } else {
+            false
+        }
+    })
+}
+
+/// A page whose functions and constants can be the target of qualified links.
+struct LinkTarget {
+    /// Module name in qualified links, e.g. `utils/math`.
+    module: String,
+    /// Path of the page, without extension.
+    page: String,
+    /// Names and anchors of the functions and constants.
+    items: Vec<(String, String)>,
+}
+
+/// Reference-style link definitions for qualified links (e.g. ``[`utils/math::clamp`]``)
+/// to the functions in all scripts.
+///
+/// `root` is the path from the current page to the root of the documentation, and
+/// `extension` the extension of the target pages.
+fn qualified_fn_links(
+    targets: &[LinkTarget],
+    current: usize,
+    root: &str,
+    extension: &str,
+) -> String {
+    targets
+        .iter()
+        .enumerate()
+        .flat_map(|(i, target)| {
+            let url = if i == current {
+                String::new()
+            } else {
+                format!("{}{}.{}", root, target.page, extension)
+            };
+
+            target
+                .items
+                .iter()
+                .map(move |(name, id)| format!("[`{}::{}`]: {}#{}\n", target.module, name, url, id))
+        })
+        .collect()
+}
+
+/// Render the imports of all scripts as a MarkDown page.
+///
+/// The graph image is inserted in place of the `<!-- graph -->` placeholder.
+fn dependencies_to_markdown(dependencies: &[data::Dependencies]) -> String {
+    let link_list = |links: &[data::Link]| {
+        if links.is_empty() {
+            return "_none_".to_string();
+        }
+        links
+            .iter()
+            .map(|data::Link { name, link }| {
+                if link.is_empty() {
+                    format!("`{}`", name)
+                } else {
+                    format!("[`{}`]({})", name, link)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut markdown = String::from("# Dependencies\n\n<!-- graph -->\n\n");
+
+    for item in dependencies {
+        match item.link {
+            Some(ref link) => markdown.push_str(&format!("## [`{}`]({})\n\n", item.name, link)),
+            None => markdown.push_str(&format!("## `{}`\n\n", item.name)),
+        }
+        markdown.push_str(&format!("- Imports: {}\n", link_list(&item.imports)));
+        markdown.push_str(&format!(
+            "- Imported by: {}\n\n",
+            link_list(&item.imported_by)
+        ));
+    }
+
+    markdown
+}
+
+/// Render the source of a script as HTML, with an anchor for each line (e.g. `#L12`).
+fn source_to_html(name: &str, script: &str, doc_link: &str) -> String {
+    let script = script.trim_end_matches(['\r', '\n']);
+
+    let lines = (1..=script.lines().count())
+        .map(|n| format!("<a id=\"L{0}\" href=\"#L{0}\">{0}</a>", n))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "<h1><code>{name}</code></h1>\n<p><a href=\"{link}\">Documentation</a></p>\n\
+         <div class=\"source-view\"><pre class=\"source-lines\">{lines}</pre>\
+         <pre class=\"source-code\"><code class=\"language-rhai hljs\">{code}</code></pre></div>\n",
+        name = name,
+        link = doc_link,
+        lines = lines,
+        code = highlight::highlight(script)
+    )
+}
+
+/// Render a script's functions as a MarkDown page.
+///
+/// Each function gets an explicit anchor so that `fn_links` (reference-style links to `#id`)
+/// resolve in the same way as in the HTML pages.
+fn script_to_markdown(
+    name: &str,
+    doc: Option<&str>,
+    constants: &[data::Constant],
+    functions: &[data::Function],
+    fn_links: &str,
+) -> String {
+    let mut markdown = match doc {
+        // The module documentation already starts with the title
+        Some(doc) if markdown_title(doc, Options::all()).is_some() => String::new(),
+        _ => format!("# `{}`\n\n", name),
+    };
+
+    if let Some(doc) = doc {
+        markdown.push_str(&strip_leading_space(doc));
+        markdown.push('\n');
+    }
+
+    let items = constants
+        .iter()
+        .map(|c| (&c.id, &c.definition, c.comments.clone(), None))
+        .chain(functions.iter().map(|f| {
+            (
+                &f.id,
+                &f.definition,
+                params::parse(&f.comments).text,
+                Some(f),
+            )
+        }));
+
+    for (id, definition, comments, function) in items {
+        markdown.push_str(&format!(
+            "\n<a id=\"{}\"></a>\n\n## `{}`\n\n",
+            id, definition
+        ));
+
+        let comments = strip_leading_space(&comments);
+        let comments = comments.trim();
+
+        if !comments.is_empty() {
+            markdown.push_str(comments);
+            markdown.push('\n');
+        }
+
+        let function = match function {
+            Some(function) => function,
+            None => continue,
+        };
+
+        // Parameters and return value, as in the `fn-block` template
+        if !function.param_docs.is_empty() {
+            markdown.push_str("\n| Parameter | Description |\n| --------- | ----------- |\n");
+
+            for param in &function.param_docs {
+                markdown.push_str(&format!(
+                    "| `{}` | {} |\n",
+                    param.name,
+                    table_cell(&param.description)
+                ));
+            }
+        }
+
+        if let Some(ref returns) = function.returns {
+            markdown.push_str(&format!(
+                "\n**Returns:** {}\n",
+                strip_leading_space(returns)
+            ));
+        }
+    }
+
+    if !fn_links.is_empty() {
+        markdown.push('\n');
+        markdown.push_str(fn_links);
+        markdown.push('\n');
+    }
+
+    markdown
+}
+
+/// Put MarkDown text on a single line, to fit in a table cell.
+fn table_cell(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+/// Remove the space conventionally following the comment leader (e.g. `///`) on each line.
+fn strip_leading_space(comments: &str) -> String {
+    comments
+        .lines()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Render doc-comments as HTML, resolving `fn_links`.
+///
+/// Code blocks without a language default to `code_lang`, and Rhai code blocks are highlighted.
+fn doc_to_html(markdown: &str, fn_links: &str, code_lang: &str, options: Options) -> String {
+    let mut markdown = markdown.to_string();
+    if !fn_links.is_empty() {
+        markdown.push_str("\n\n");
+        markdown.push_str(fn_links);
+    }
+
+    let mut events = Vec::new();
+    // Rhai code block being collected for highlighting at build time
+    let mut rhai_code: Option<String> = None;
+
+    for event in Parser::new_ext(&markdown, options) {
+        match (event, &mut rhai_code) {
+            (Event::Start(Tag::CodeBlock(kind)), _) => {
+                // Attributes of code examples (e.g. `no_run`) are not part of the language
+                let lang = match kind {
+                    CodeBlockKind::Fenced(ref info) => doctest::code_lang(info),
+                    CodeBlockKind::Indented => None,
+                }
+                .unwrap_or(code_lang)
+                .to_string();
+
+                if lang == "rhai" {
+                    rhai_code = Some(String::new());
+                } else {
+                    events.push(Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(
+                        lang.into(),
+                    ))));
+                }
+            }
+            (Event::Text(text), Some(code)) => code.push_str(&text),
+            (Event::End(Tag::CodeBlock(_)), Some(code)) => {
+                events.push(Event::Html(
+                    format!(
+                        "<pre><code class=\"language-rhai hljs\">{}</code></pre>\n",
+                        highlight::highlight(code)
+                    )
+                    .into(),
+                ));
+                rhai_code = None;
+            }
+            (event, _) => events.push(event),
+        }
+    }
+
+    let mut html_output = String::new();
+    html::push_html(&mut html_output, events.into_iter());
+    html_output
+}
+
+handlebars_helper!(highlight_helper: |code: str| highlight::highlight(code));
+
+/// Find all reference-style links in a MarkDown document that have no matching definition.
+///
+/// Function links (`fn_links`) are appended to the document first, as in [`doc_to_html`].
+fn broken_links(markdown: &str, fn_links: &str, options: Options) -> Vec<String> {
+    let mut markdown = markdown.to_string();
+    if !fn_links.is_empty() {
+        markdown.push_str("\n\n");
+        markdown.push_str(fn_links);
+    }
+
+    let mut references = Vec::new();
+    let mut callback = |link: BrokenLink| {
+        let reference = link.reference.to_string();
+        if !references.contains(&reference) {
+            references.push(reference);
+        }
+        None
+    };
+
+    Parser::new_with_broken_link_callback(&markdown, options, Some(&mut callback)).for_each(drop);
+
+    references
+}
+
+/// Percentage of documented functions, rounded down to one decimal place, or 100 when there are
+/// no functions at all.
+fn coverage_percent(documented: usize, total: usize) -> f64 {
+    match (documented * 1000).checked_div(total) {
+        Some(permille) => permille as f64 / 10.0,
+        None => 100.0,
+    }
+}
+
+/// Generate an SVG badge (in the style of `shields.io`) showing the documentation coverage.
+fn coverage_badge(percent: f64) -> String {
+    let color = match percent {
+        p if p >= 90.0 => "#4c1",
+        p if p >= 75.0 => "#dfb317",
+        p if p >= 50.0 => "#fe7d37",
+        _ => "#e05d44",
+    };
+    let value = format!("{}%", percent);
+
+    std::str::from_utf8(include_bytes!("../assets/coverage.svg"))
+        .unwrap()
+        .replace("{{color}}", color)
+        .replace("{{value}}", &value)
+}
+
+/// Remove the enclosing paragraph from HTML consisting of a single paragraph,
+/// e.g. for use inside a table cell.
+fn inline_html(html: &str) -> String {
+    let html = html.trim();
+
+    match html
+        .strip_prefix("<p>")
+        .and_then(|html| html.strip_suffix("</p>"))
+    {
+        Some(inner) if !inner.contains("<p>") => inner.to_string(),
+        _ => html.to_string(),
+    }
+}
+
+/// Get the title of a MarkDown document, if it starts with a level-1 heading.
+fn markdown_title(markdown: &str, options: Options) -> Option<String> {
+    let mut parser = Parser::new_ext(markdown, options);
+    let h1 = Tag::Heading(HeadingLevel::H1, None, Default::default());
+
+    if parser.next() != Some(Event::Start(h1)) {
+        return None;
+    }
+
+    let mut title = String::new();
+
+    for event in parser {
+        match event {
+            Event::Text(text) | Event::Code(text) => title.push_str(&text),
+            Event::End(Tag::Heading(..)) => break,
+            _ => (),
+        }
+    }
+
+    let title = title.trim();
+
+    if title.is_empty() {
+        None
+    } else {
+        Some(title.to_string())
+    }
+}
+
+fn write_file(path: &Path, content: &[u8]) -> Result<(), error::RhaiDocError> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = File::create(path)?;
+    file.write_all(content)?;
+    Ok(())
+}
+
+/// Extract the plain text from MarkDown, for indexing.
+fn markdown_to_text(markdown: &str, options: Options) -> String {
+    let mut text = String::new();
+
+    for event in Parser::new_ext(markdown, options) {
+        match event {
+            Event::Text(s) | Event::Code(s) => text.push_str(&s),
+            Event::SoftBreak
+            | Event::HardBreak
+            | Event::End(Tag::Paragraph)
+            | Event::End(Tag::Heading(..))
+            | Event::End(Tag::Item)
+            | Event::End(Tag::CodeBlock(_))
+            | Event::End(Tag::TableCell) => text.push(' '),
+            _ => (),
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn html_from_pathbuf(path: &Path, root: &Path) -> PathBuf {
+    let mut new_path = path
+        .strip_prefix(root)
+        .map_or_else(|_| PathBuf::from(path), PathBuf::from);
+    new_path.set_extension("html");
+    new_path
+}
+
+/// Get the path of a directory relative to the root of the git repository containing it
+/// (e.g. `scripts/`, or empty at the root).
+fn git_prefix(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-prefix"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|prefix| prefix.trim().to_string())
+}
+
+/// Get the revision checked out in the git repository containing a directory.
+fn git_revision(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let rev = String::from_utf8(output.stdout).ok()?;
+    Some(rev.trim().to_string()).filter(|rev| !rev.is_empty())
+}
+
+/// Path of the source page of a script (e.g. `source/utils/math.rhai.html`).
+fn source_from_pathbuf(path: &Path, root: &Path) -> PathBuf {
+    let mut file = path
+        .strip_prefix(root)
+        .unwrap_or(path)
+        .as_os_str()
+        .to_owned();
+    file.push(".html");
+    Path::new("source").join(file)
+}
+
+fn link_from_pathbuf(path: &Path) -> String {
+    path.components()
+        .map(|s| s.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn gen_hash_name(name: &str, num_params: usize) -> String {
+    if num_params == 0 {
+        name.to_string()
+    } else {
+        format!("{}-{}", name, num_params)
+    }
+}
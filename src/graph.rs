@@ -0,0 +1,254 @@
+//! Import dependencies between scripts.
+
+use crate::data;
+use rhai::{ASTNode, Expr, Stmt, AST};
+use std::path::{Path, PathBuf};
+
+/// A script (or an unresolved module) in the dependency graph.
+#[derive(Debug, Clone)]
+pub struct Node {
+    /// Module path of the script (e.g. `utils/math`), or the import path if not resolved.
+    pub name: String,
+    /// Link to the documentation of the script, if documented.
+    pub link: Option<String>,
+}
+
+/// Import dependencies between scripts.
+#[derive(Debug, Clone, Default)]
+pub struct ImportGraph {
+    pub nodes: Vec<Node>,
+    /// Edges from the importing node to the imported node.
+    pub edges: Vec<(usize, usize)>,
+}
+
+/// Find the paths of all `import` statements with a constant path in a script,
+/// including those inside functions.
+pub fn imports(ast: &AST) -> Vec<String> {
+    let mut paths = Vec::new();
+
+    ast.walk(&mut |nodes| {
+        if let Some(ASTNode::Stmt(Stmt::Import(x, ..))) = nodes.last() {
+            if let Expr::StringConstant(ref path, ..) = x.0 {
+                if !paths.iter().any(|p| p == path.as_str()) {
+                    paths.push(path.to_string());
+                }
+            }
+        }
+        true
+    });
+
+    paths
+}
+
+/// Resolve an import path to a script file in the same way as Rhai's `FileModuleResolver`,
+/// first against the scripts directory, then against the directory of the importing script.
+pub fn resolve(import: &str, script: &Path, source: &Path, extension: &str) -> Option<PathBuf> {
+    let bases = [Some(source), script.parent()];
+
+    bases.iter().flatten().find_map(|base| {
+        let mut path = base.join(import);
+        path.set_extension(extension);
+        path.canonicalize().ok()
+    })
+}
+
+impl ImportGraph {
+    /// Get the index of a node by name, adding it if not yet in the graph.
+    pub fn node(&mut self, name: &str, link: Option<&str>) -> usize {
+        match self.nodes.iter().position(|n| n.name == name) {
+            Some(index) => index,
+            None => {
+                self.nodes.push(Node {
+                    name: name.to_string(),
+                    link: link.map(|s| s.to_string()),
+                });
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Add an import between two nodes.
+    pub fn add_import(&mut self, from: usize, to: usize) {
+        if from != to && !self.edges.contains(&(from, to)) {
+            self.edges.push((from, to));
+        }
+    }
+
+    /// Imports and importers of each node.
+    pub fn dependencies(&self) -> Vec<data::Dependencies> {
+        let link = |index: usize| data::Link {
+            name: self.nodes[index].name.clone(),
+            link: self.nodes[index].link.clone().unwrap_or_default(),
+        };
+
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| data::Dependencies {
+                name: node.name.clone(),
+                link: node.link.clone(),
+                imports: self
+                    .edges
+                    .iter()
+                    .filter(|&&(from, _)| from == i)
+                    .map(|&(_, to)| link(to))
+                    .collect(),
+                imported_by: self
+                    .edges
+                    .iter()
+                    .filter(|&&(_, to)| to == i)
+                    .map(|&(from, _)| link(from))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Render the graph in the DOT language of Graphviz.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph imports {\n    node [shape=box];\n");
+
+        for node in &self.nodes {
+            match node.link {
+                Some(ref link) => dot.push_str(&format!("    {:?} [URL={:?}];\n", node.name, link)),
+                None => dot.push_str(&format!("    {:?} [style=dashed];\n", node.name)),
+            }
+        }
+        for &(from, to) in &self.edges {
+            dot.push_str(&format!(
+                "    {:?} -> {:?};\n",
+                self.nodes[from].name, self.nodes[to].name
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Layer of each node, with importing scripts above the scripts they import.
+    ///
+    /// Imports that form a cycle are ignored once the layers stop changing.
+    fn layers(&self) -> Vec<usize> {
+        let mut layers = vec![0; self.nodes.len()];
+
+        for _ in 0..self.nodes.len() {
+            let mut changed = false;
+
+            for &(from, to) in &self.edges {
+                if layers[to] <= layers[from] && layers[from] + 1 < self.nodes.len() {
+                    layers[to] = layers[from] + 1;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        layers
+    }
+
+    /// Render the graph as an SVG image, with nodes arranged in layers.
+    ///
+    /// Links in the image are relative to the root of the documentation.
+    pub fn to_svg(&self, color: &str) -> String {
+        const CHAR_WIDTH: usize = 8;
+        const PADDING: usize = 12;
+        const HEIGHT: usize = 28;
+        const H_GAP: usize = 24;
+        const V_GAP: usize = 48;
+
+        let layers = self.layers();
+        let num_layers = layers.iter().max().map_or(0, |n| n + 1);
+
+        // Position of each node: (x, y, width)
+        let mut positions = vec![(0, 0, 0); self.nodes.len()];
+        let mut layer_widths = vec![0; num_layers];
+
+        for (i, node) in self.nodes.iter().enumerate() {
+            let layer = layers[i];
+            let width = node.name.chars().count() * CHAR_WIDTH + 2 * PADDING;
+
+            if layer_widths[layer] > 0 {
+                layer_widths[layer] += H_GAP;
+            }
+            positions[i] = (layer_widths[layer], layer * (HEIGHT + V_GAP), width);
+            layer_widths[layer] += width;
+        }
+
+        // Center each layer
+        let total_width = layer_widths.iter().copied().max().unwrap_or(0);
+
+        for (i, position) in positions.iter_mut().enumerate() {
+            position.0 += (total_width - layer_widths[layers[i]]) / 2;
+        }
+
+        let total_height = (num_layers * (HEIGHT + V_GAP)).saturating_sub(V_GAP);
+
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" class="import-graph" width="{w}" height="{h}" viewBox="-2 -2 {w} {h}" font-family="sans-serif" font-size="13">"#,
+            w = total_width + 4,
+            h = total_height + 4
+        );
+        svg.push_str(&format!(
+            r#"<defs><marker id="arrow" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="8" markerHeight="8" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 z" fill="{}"/></marker></defs>"#,
+            color
+        ));
+
+        for &(from, to) in &self.edges {
+            let (x1, y1, w1) = positions[from];
+            let (x2, y2, w2) = positions[to];
+            let (y1, y2) = if y1 < y2 {
+                (y1 + HEIGHT, y2)
+            } else {
+                (y1, y2 + HEIGHT)
+            };
+
+            svg.push_str(&format!(
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" marker-end="url(#arrow)"/>"#,
+                x1 + w1 / 2,
+                y1,
+                x2 + w2 / 2,
+                y2,
+                color
+            ));
+        }
+
+        for (node, &(x, y, width)) in self.nodes.iter().zip(positions.iter()) {
+            let dash = if node.link.is_some() {
+                ""
+            } else {
+                r#" stroke-dasharray="4 2""#
+            };
+            let shape = format!(
+                r#"<rect x="{x}" y="{y}" width="{w}" height="{h}" rx="4" fill="white" stroke="{c}"{d}/><text x="{tx}" y="{ty}" text-anchor="middle" dominant-baseline="central">{name}</text>"#,
+                x = x,
+                y = y,
+                w = width,
+                h = HEIGHT,
+                c = color,
+                d = dash,
+                tx = x + width / 2,
+                ty = y + HEIGHT / 2,
+                name = escape_xml(&node.name)
+            );
+
+            match node.link {
+                Some(ref link) => {
+                    svg.push_str(&format!(r#"<a href="{}">{}</a>"#, escape_xml(link), shape))
+                }
+                None => svg.push_str(&shape),
+            }
+        }
+
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
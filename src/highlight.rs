@@ -0,0 +1,322 @@
+//! Build-time syntax highlighting of Rhai code.
+//!
+//! Code is split into HTML spans with the same CSS classes as
+//! [`highlight.js`](https://highlightjs.org/), so the `code_theme` stylesheets also apply.
+
+use pulldown_cmark::escape::escape_html;
+
+const KEYWORDS: &[&str] = &[
+    "as", "break", "catch", "const", "continue", "do", "else", "export", "fn", "for", "global",
+    "if", "import", "in", "let", "loop", "private", "return", "switch", "this", "throw", "try",
+    "until", "while",
+];
+
+const LITERALS: &[&str] = &["true", "false"];
+
+const BUILT_INS: &[&str] = &[
+    "Fn",
+    "call",
+    "curry",
+    "debug",
+    "eval",
+    "is_def_fn",
+    "is_def_var",
+    "is_shared",
+    "print",
+    "type_of",
+];
+
+/// Highlight Rhai code as HTML.
+pub fn highlight(code: &str) -> String {
+    let mut highlighter = Highlighter {
+        code,
+        pos: 0,
+        html: String::new(),
+    };
+    highlighter.run();
+    highlighter.html
+}
+
+struct Highlighter<'a> {
+    code: &'a str,
+    pos: usize,
+    html: String,
+}
+
+impl<'a> Highlighter<'a> {
+    fn rest(&self) -> &'a str {
+        &self.code[self.pos..]
+    }
+
+    /// Write the next `len` bytes of code as plain text.
+    fn plain(&mut self, len: usize) {
+        let text = &self.code[self.pos..self.pos + len];
+        escape_html(&mut self.html, text).unwrap();
+        self.pos += len;
+    }
+
+    /// Write the next `len` bytes of code in a span with a `hljs-` class.
+    fn span(&mut self, class: &str, len: usize) {
+        if len == 0 {
+            return;
+        }
+        self.html.push_str("<span class=\"hljs-");
+        self.html.push_str(class);
+        self.html.push_str("\">");
+        self.plain(len);
+        self.html.push_str("</span>");
+    }
+
+    fn run(&mut self) {
+        // Is the next identifier the name of a function definition?
+        let mut after_fn = false;
+
+        while let Some(c) = self.rest().chars().next() {
+            let rest = self.rest();
+
+            if c.is_whitespace() {
+                self.plain(c.len_utf8());
+                continue;
+            }
+
+            if rest.starts_with("//") {
+                self.span("comment", rest.find('\n').unwrap_or(rest.len()));
+            } else if rest.starts_with("/*") {
+                self.span("comment", block_comment_len(rest));
+            } else if c == '"' || c == '\'' {
+                self.span("string", quoted_len(rest));
+            } else if c == '`' {
+                self.template_string();
+            } else if c.is_ascii_digit() {
+                self.span("number", number_len(rest));
+            } else if c.is_alphabetic() || c == '_' {
+                let len = rest
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+                let word = &rest[..len];
+
+                if KEYWORDS.contains(&word) {
+                    self.span("keyword", len);
+                    after_fn = word == "fn";
+                    continue;
+                } else if LITERALS.contains(&word) {
+                    self.span("literal", len);
+                } else if after_fn && rest[len..].starts_with("::") {
+                    // Namespace of a qualified function name (e.g. `math::clamp`)
+                    self.plain(len + 2);
+                    continue;
+                } else if after_fn {
+                    self.span("title", len);
+                } else if BUILT_INS.contains(&word) {
+                    self.span("built_in", len);
+                } else {
+                    self.plain(len);
+                }
+            } else {
+                self.plain(c.len_utf8());
+            }
+
+            after_fn = false;
+        }
+    }
+
+    /// Highlight a back-tick string, with interpolated expressions (`${...}`) as code.
+    fn template_string(&mut self) {
+        // Skip the opening back-tick
+        let mut len = 1;
+
+        loop {
+            let rest = &self.rest()[len..];
+
+            if rest.is_empty() {
+                self.span("string", len);
+                return;
+            } else if rest.starts_with("``") {
+                len += 2;
+            } else if rest.starts_with('`') {
+                self.span("string", len + 1);
+                return;
+            } else if rest.starts_with("${") {
+                self.span("string", len);
+
+                let rest = &self.rest()[2..];
+                let inner = match closing_brace(rest) {
+                    Some(end) => &rest[..end],
+                    None => rest,
+                };
+
+                self.html.push_str("<span class=\"hljs-subst\">${");
+                self.html.push_str(&highlight(inner));
+                self.pos += 2 + inner.len();
+
+                if self.rest().starts_with('}') {
+                    self.html.push('}');
+                    self.pos += 1;
+                }
+                self.html.push_str("</span>");
+
+                len = 0;
+            } else {
+                len += rest.chars().next().unwrap().len_utf8();
+            }
+        }
+    }
+}
+
+/// Length of a (possibly nested) block comment.
+fn block_comment_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut depth = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"/*") {
+            depth += 1;
+            i += 2;
+        } else if bytes[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+
+    bytes.len()
+}
+
+/// Length of a string or character literal, up to the end of the line if not terminated.
+fn quoted_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let quote = bytes[0];
+    let mut i = 1;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'\n' => return i,
+            b if b == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+
+    bytes.len()
+}
+
+/// Length of a number literal (e.g. `42`, `1_000`, `0xff`, `1.5e-3`).
+fn number_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let digits = |from: usize, radix: u32| {
+        bytes[from..]
+            .iter()
+            .position(|&b| !(b as char).is_digit(radix) && b != b'_')
+            .map_or(bytes.len(), |n| from + n)
+    };
+
+    if bytes.len() > 2 && bytes[0] == b'0' {
+        match bytes[1] {
+            b'x' => return digits(2, 16),
+            b'o' => return digits(2, 8),
+            b'b' => return digits(2, 2),
+            _ => (),
+        }
+    }
+
+    let mut i = digits(0, 10);
+
+    if bytes.get(i) == Some(&b'.') && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
+        i = digits(i + 1, 10);
+    }
+
+    if matches!(bytes.get(i), Some(b'e') | Some(b'E')) {
+        let sign = usize::from(matches!(bytes.get(i + 1), Some(b'+') | Some(b'-')));
+
+        if bytes.get(i + 1 + sign).is_some_and(u8::is_ascii_digit) {
+            i = digits(i + 1 + sign, 10);
+        }
+    }
+
+    i
+}
+
+/// Position of the `}` closing an interpolated expression, skipping nested blocks and strings.
+fn closing_brace(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut depth = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' if depth == 0 => return Some(i),
+            b'}' => depth -= 1,
+            b'"' | b'\'' => {
+                i += quoted_len(&text[i..]);
+                continue;
+            }
+            _ => (),
+        }
+        i += 1;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlights_tokens() {
+        assert_eq!(
+            highlight("let x = 0x1f; // note"),
+            r#"<span class="hljs-keyword">let</span> x = <span class="hljs-number">0x1f</span>; <span class="hljs-comment">// note</span>"#
+        );
+        assert_eq!(
+            highlight(r#"print("a<b", true)"#),
+            r#"<span class="hljs-built_in">print</span>(<span class="hljs-string">&quot;a&lt;b&quot;</span>, <span class="hljs-literal">true</span>)"#
+        );
+    }
+
+    #[test]
+    fn highlights_function_names() {
+        assert_eq!(
+            highlight("fn add(a, b)"),
+            r#"<span class="hljs-keyword">fn</span> <span class="hljs-title">add</span>(a, b)"#
+        );
+        assert_eq!(
+            highlight("fn math::clamp(x)"),
+            r#"<span class="hljs-keyword">fn</span> math::<span class="hljs-title">clamp</span>(x)"#
+        );
+    }
+
+    #[test]
+    fn highlights_interpolation() {
+        assert_eq!(
+            highlight("`a ${x + 1} b`"),
+            r#"<span class="hljs-string">`a </span><span class="hljs-subst">${x + <span class="hljs-number">1</span>}</span><span class="hljs-string"> b`</span>"#
+        );
+    }
+
+    #[test]
+    fn tolerates_unterminated_tokens() {
+        assert_eq!(
+            highlight("\"abc\nx"),
+            "<span class=\"hljs-string\">&quot;abc</span>\nx"
+        );
+        assert_eq!(
+            highlight("/* a /* b */"),
+            r#"<span class="hljs-comment">/* a /* b */</span>"#
+        );
+        assert_eq!(
+            highlight("`a ${x"),
+            r#"<span class="hljs-string">`a </span><span class="hljs-subst">${x</span>"#
+        );
+        assert_eq!(
+            highlight("`abc"),
+            r#"<span class="hljs-string">`abc</span>"#
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! `rhai-doc` - documentation tool for [Rhai](https://rhai.rs) scripts.
+//!
+//! The [`DocGenerator`] type drives documentation generation and can be used from build scripts,
+//! tests or host applications without spawning the `rhai-doc` binary.
+//!
+//! ```no_run
+//! use rhai_doc::{config::Config, DocGenerator};
+//!
+//! let config = Config::load("scripts/rhai.toml")?;
+//!
+//! let summary = DocGenerator::new(config)
+//!     .source("scripts")
+//!     .destination("dist")
+//!     .quiet(true)
+//!     .generate()?;
+//!
+//! println!("{} script(s) documented", summary.scripts);
+//! # Ok::<(), rhai_doc::error::RhaiDocError>(())
+//! ```
+
+use rhai::AST;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! write_log {
+    ($debug:expr, $fmt:expr, $(@ $args:expr),*) => {
+        if $debug { println!($fmt, $($args.to_string_lossy()),*); }
+    };
+    ($debug:expr, $($args:expr),*) => {
+        if $debug { println!($($args),*); }
+    };
+}
+
+pub mod config;
+pub mod data;
+mod doctest;
+pub mod error;
+mod front_matter;
+mod generator;
+mod graph;
+mod highlight;
+mod metadata;
+mod nav;
+mod palette;
+mod params;
+mod source;
+
+pub use doctest::TestSummary;
+pub use generator::{CompileError, DocGenerator, OutputFormat, Summary, Warning};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LinkInfo {
+    pub path: PathBuf,
+    pub active: bool,
+    pub name: String,
+    pub link: String,
+    pub sub_links: Vec<data::Link>,
+    #[serde(skip)]
+    pub ast: Option<AST>,
+    #[serde(skip)]
+    pub doc: Option<String>,
+}
@@ -0,0 +1,71 @@
+//! Function metadata JSON exported by a host application via
+//! [`Engine::gen_fn_metadata_to_json`][rhai::Engine::gen_fn_metadata_to_json].
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleMetadata {
+    #[serde(default)]
+    pub doc: String,
+    #[serde(default)]
+    pub modules: BTreeMap<String, ModuleMetadata>,
+    #[serde(default)]
+    pub functions: Vec<FnMetadata>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FnMetadata {
+    pub access: String,
+    pub name: String,
+    pub num_params: usize,
+    #[serde(default)]
+    pub params: Vec<FnParam>,
+    pub signature: String,
+    #[serde(default)]
+    pub doc_comments: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct FnParam {
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub typ: Option<String>,
+}
+
+impl ModuleMetadata {
+    /// All functions in this module and its sub-modules, each with its qualified name
+    /// (e.g. `math::clamp` for `clamp` in the sub-module `math`).
+    pub fn functions(&self) -> Vec<(String, &FnMetadata)> {
+        let mut functions = self
+            .functions
+            .iter()
+            .map(|f| (f.name.clone(), f))
+            .collect::<Vec<_>>();
+
+        for (name, module) in &self.modules {
+            functions.extend(
+                module
+                    .functions()
+                    .into_iter()
+                    .map(|(qualified, f)| (format!("{}::{}", name, qualified), f)),
+            );
+        }
+
+        functions
+    }
+}
+
+impl FnParam {
+    /// Format the parameter as `name: type`.
+    pub fn to_param_string(&self) -> String {
+        let name = self.name.as_deref().unwrap_or("_");
+
+        match self.typ {
+            Some(ref typ) => format!("{}: {}", name, typ),
+            None => name.to_string(),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Navigation of the MarkDown pages, as a tree of sections mirroring the pages directory.
+
+use crate::data::{NavItem, Navigation};
+use crate::LinkInfo;
+use std::path::Path;
+
+/// File name (without extension) of the page of a section.
+pub const SECTION_INDEX: &str = "_index";
+
+/// Build the navigation from the page links, with the active link as the current page.
+///
+/// Pages are grouped into sections by their directory under the pages directory, and a section
+/// takes the position of its first page. Links outside the pages directory (e.g. the dependencies
+/// page) are top-level items.
+pub fn navigation(links: &[LinkInfo], pages_dir: &Path) -> Navigation {
+    let mut tree = Vec::new();
+
+    for info in links {
+        let path = info.path.strip_prefix(pages_dir).unwrap_or(&info.path);
+
+        let dirs = path
+            .parent()
+            .map(|dir| {
+                dir.iter()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let is_index =
+            !dirs.is_empty() && path.file_stem().is_some_and(|stem| stem == SECTION_INDEX);
+
+        insert(&mut tree, &dirs, info, is_index);
+    }
+
+    mark_open(&mut tree);
+
+    let mut breadcrumbs = Vec::new();
+    trail(&tree, &mut breadcrumbs);
+
+    // Top-level pages have no breadcrumbs
+    if breadcrumbs.len() < 2 {
+        breadcrumbs.clear();
+    }
+
+    let mut flat = Vec::new();
+    flatten(&tree, &mut flat);
+
+    let (prev, next) = match flat.iter().position(|item| item.active) {
+        Some(n) => (
+            n.checked_sub(1).map(|n| flat[n].clone()),
+            flat.get(n + 1).cloned(),
+        ),
+        None => (None, None),
+    };
+
+    Navigation {
+        tree,
+        breadcrumbs,
+        prev,
+        next,
+    }
+}
+
+/// Insert a page under its sections, creating them as needed.
+fn insert(items: &mut Vec<NavItem>, dirs: &[String], info: &LinkInfo, is_index: bool) {
+    let (dir, rest) = match dirs.split_first() {
+        Some(split) => split,
+        None => {
+            items.push(NavItem {
+                name: info.name.clone(),
+                link: Some(info.link.clone()),
+                active: info.active,
+                ..Default::default()
+            });
+            return;
+        }
+    };
+
+    let n = match items.iter().position(|item| item.dir.as_ref() == Some(dir)) {
+        Some(n) => n,
+        None => {
+            items.push(NavItem {
+                name: dir.clone(),
+                dir: Some(dir.clone()),
+                ..Default::default()
+            });
+            items.len() - 1
+        }
+    };
+
+    let section = &mut items[n];
+
+    if rest.is_empty() && is_index {
+        section.name = info.name.clone();
+        section.link = Some(info.link.clone());
+        section.active = info.active;
+    } else {
+        insert(&mut section.children, rest, info, is_index);
+    }
+}
+
+/// Open the sections containing the current page; returns whether any item is (or contains) it.
+fn mark_open(items: &mut [NavItem]) -> bool {
+    let mut found = false;
+
+    for item in items {
+        item.open = mark_open(&mut item.children) || item.active;
+        found |= item.open;
+    }
+
+    found
+}
+
+/// Collect the items leading to the current page; returns whether it was found.
+fn trail(items: &[NavItem], breadcrumbs: &mut Vec<NavItem>) -> bool {
+    for item in items.iter().filter(|item| item.open) {
+        breadcrumbs.push(leaf(item));
+
+        if item.active || trail(&item.children, breadcrumbs) {
+            return true;
+        }
+
+        breadcrumbs.pop();
+    }
+
+    false
+}
+
+/// Collect all the items with a link, in reading order.
+fn flatten(items: &[NavItem], flat: &mut Vec<NavItem>) {
+    for item in items {
+        if item.link.is_some() {
+            flat.push(leaf(item));
+        }
+        flatten(&item.children, flat);
+    }
+}
+
+/// Copy of an item, without its children.
+fn leaf(item: &NavItem) -> NavItem {
+    NavItem {
+        name: item.name.clone(),
+        link: item.link.clone(),
+        active: item.active,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,150 @@
+//! Light and dark color palettes derived from the theme color.
+
+use crate::config::{PaletteColors, Rgb};
+use serde::Serialize;
+
+/// Minimum contrast ratio of text against its background (WCAG AA).
+const TEXT_CONTRAST: f64 = 4.5;
+
+const WHITE: Rgb = Rgb(255, 255, 255);
+const BLACK: Rgb = Rgb(0, 0, 0);
+
+/// CSS colors of a palette, as used by `styles.tpl.css`.
+#[derive(Serialize, Debug, Clone)]
+pub struct Palette {
+    pub background: String,
+    pub surface: String,
+    pub sidebar: String,
+    pub private: String,
+    pub code_background: String,
+    pub text: String,
+    pub muted: String,
+    pub border: String,
+    pub accent: String,
+    pub accent_alpha: String,
+    pub link: String,
+}
+
+impl Palette {
+    /// Light palette, close to the original neutral grays and tinted with the theme color.
+    pub fn light(color: Rgb, overrides: &PaletteColors) -> Self {
+        let accent = overrides.accent.unwrap_or(color);
+        let background = overrides
+            .background
+            .unwrap_or_else(|| mix(Rgb(240, 242, 244), accent, 0.03));
+        let surface = overrides.surface.unwrap_or(WHITE);
+
+        Self::new(
+            overrides,
+            accent,
+            background,
+            surface,
+            Rgb(34, 34, 34),
+            Rgb(119, 119, 119),
+            Rgb(212, 218, 223),
+        )
+    }
+
+    /// Dark palette, with near-black grays tinted with the theme color.
+    pub fn dark(color: Rgb, overrides: &PaletteColors) -> Self {
+        let accent = overrides
+            .accent
+            .unwrap_or_else(|| readable(color, Rgb(18, 18, 20), 3.0));
+        let background = overrides
+            .background
+            .unwrap_or_else(|| mix(Rgb(18, 18, 20), accent, 0.04));
+        let surface = overrides
+            .surface
+            .unwrap_or_else(|| mix(Rgb(30, 31, 34), accent, 0.05));
+
+        Self::new(
+            overrides,
+            accent,
+            background,
+            surface,
+            Rgb(230, 230, 230),
+            Rgb(160, 160, 160),
+            mix(Rgb(60, 62, 66), accent, 0.05),
+        )
+    }
+
+    /// Fill in the text colors and derived shades, keeping any overrides.
+    fn new(
+        overrides: &PaletteColors,
+        accent: Rgb,
+        background: Rgb,
+        surface: Rgb,
+        text: Rgb,
+        muted: Rgb,
+        border: Rgb,
+    ) -> Self {
+        let text = overrides
+            .text
+            .unwrap_or_else(|| readable(text, surface, TEXT_CONTRAST));
+        let muted = overrides
+            .muted
+            .unwrap_or_else(|| readable(muted, surface, TEXT_CONTRAST));
+        let border = overrides.border.unwrap_or(border);
+        let link = overrides
+            .link
+            .unwrap_or_else(|| readable(accent, surface, TEXT_CONTRAST));
+
+        Self {
+            background: background.to_string(),
+            surface: surface.to_string(),
+            sidebar: mix(background, surface, 0.5).to_string(),
+            private: mix(surface, Rgb(119, 119, 255), 0.14).to_string(),
+            code_background: mix(background, surface, 0.6).to_string(),
+            text: text.to_string(),
+            muted: muted.to_string(),
+            border: border.to_string(),
+            accent: accent.to_string(),
+            accent_alpha: accent.to_alpha(45).to_string(),
+            link: link.to_string(),
+        }
+    }
+}
+
+/// Mix a fraction (0 to 1) of one color into another.
+fn mix(base: Rgb, other: Rgb, fraction: f64) -> Rgb {
+    let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * fraction).round() as u8;
+    Rgb(
+        channel(base.0, other.0),
+        channel(base.1, other.1),
+        channel(base.2, other.2),
+    )
+}
+
+/// Relative luminance of a color, as defined by WCAG.
+fn luminance(color: Rgb) -> f64 {
+    let channel = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(color.0) + 0.7152 * channel(color.1) + 0.0722 * channel(color.2)
+}
+
+/// Contrast ratio between two colors, from 1 to 21.
+fn contrast(a: Rgb, b: Rgb) -> f64 {
+    let (a, b) = (luminance(a), luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Darken (or lighten, on a dark background) a color until it has at least the given contrast
+/// ratio against a background.
+fn readable(color: Rgb, background: Rgb, ratio: f64) -> Rgb {
+    let target = if luminance(background) > 0.5 {
+        BLACK
+    } else {
+        WHITE
+    };
+
+    (0..=20)
+        .map(|step| mix(color, target, step as f64 / 20.0))
+        .find(|&shade| contrast(shade, background) >= ratio)
+        .unwrap_or(target)
+}
@@ -0,0 +1,264 @@
+//! Structured documentation of parameters and return values in doc-comments.
+//!
+//! Two conventions are recognized:
+//!
+//! * `# Parameters` and `# Returns` headings (any level), with one `` `name` - description ``
+//!   line (or list item) per parameter;
+//! * `@param name description` and `@returns description` tags.
+
+/// Doc-comment text split into its description and structured parts.
+#[derive(Debug, Clone, Default)]
+pub struct StructuredDoc {
+    /// Doc-comment text, without the structured parts.
+    pub text: String,
+    /// Documented parameters (name and description), if any are documented.
+    pub params: Option<Vec<(String, String)>>,
+    /// Description of the return value, if any.
+    pub returns: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    None,
+    Params,
+    Returns,
+}
+
+/// Split the structured parameters and return value documentation out of a doc-comment.
+pub fn parse(markdown: &str) -> StructuredDoc {
+    let mut doc = StructuredDoc::default();
+    let mut text = Vec::new();
+    let mut params: Vec<(String, String)> = Vec::new();
+    let mut has_params = false;
+    let mut returns: Vec<&str> = Vec::new();
+    let mut section = Section::None;
+    // Parameter (or return value) whose description continues on the next line
+    let mut current = Section::None;
+    let mut in_fence = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+
+        if in_fence || trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            if section == Section::Returns {
+                returns.push(line);
+            } else {
+                text.push(line);
+            }
+            continue;
+        }
+
+        if let Some(title) = heading(trimmed) {
+            section = match title.to_lowercase().as_str() {
+                "parameters" | "params" | "arguments" => {
+                    has_params = true;
+                    Section::Params
+                }
+                "returns" | "return" | "return value" => Section::Returns,
+                _ => {
+                    text.push(line);
+                    Section::None
+                }
+            };
+            current = section;
+            continue;
+        }
+
+        if let Some(rest) = trimmed.strip_prefix("@param ") {
+            let rest = rest.trim();
+            let (name, description) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            let description = description
+                .trim_start()
+                .trim_start_matches(['-', ':'])
+                .trim_start();
+            params.push((name.trim_matches('`').to_string(), description.to_string()));
+            has_params = true;
+            current = Section::Params;
+            continue;
+        }
+        if let Some(rest) = trimmed
+            .strip_prefix("@returns")
+            .or_else(|| trimmed.strip_prefix("@return"))
+        {
+            returns.push(rest.trim());
+            current = Section::Returns;
+            continue;
+        }
+
+        match section {
+            Section::Params if trimmed.is_empty() => current = Section::None,
+            Section::Params => match split_param(trimmed) {
+                Some((name, description)) => {
+                    params.push((name.to_string(), description.to_string()));
+                    current = Section::Params;
+                }
+                None if current == Section::Params && !params.is_empty() => {
+                    append(&mut params.last_mut().unwrap().1, trimmed)
+                }
+                None => text.push(line),
+            },
+            Section::Returns => returns.push(line),
+            Section::None if trimmed.is_empty() => {
+                current = Section::None;
+                text.push(line);
+            }
+            Section::None => match current {
+                Section::Params if !params.is_empty() => {
+                    append(&mut params.last_mut().unwrap().1, trimmed)
+                }
+                Section::Returns => returns.push(trimmed),
+                _ => text.push(line),
+            },
+        }
+    }
+
+    doc.text = text.join("\n");
+    if has_params {
+        doc.params = Some(params);
+    }
+    let returns = returns.join("\n");
+    if !returns.trim().is_empty() {
+        doc.returns = Some(returns.trim().to_string());
+    }
+    doc
+}
+
+/// Get the title of a MarkDown ATX heading (e.g. `## Title`).
+fn heading(line: &str) -> Option<&str> {
+    let title = line.trim_start_matches('#');
+    let level = line.len() - title.len();
+
+    if (1..=6).contains(&level) && title.starts_with(' ') {
+        Some(title.trim().trim_end_matches('#').trim())
+    } else {
+        None
+    }
+}
+
+/// Split a parameter entry (e.g. `` - `name` - description `` or `name: description`) into the
+/// parameter name and its description.
+fn split_param(line: &str) -> Option<(&str, &str)> {
+    let item = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "));
+    let is_item = item.is_some();
+    let line = item.unwrap_or(line).trim_start();
+
+    let (name, rest, quoted) = match line.strip_prefix('`') {
+        Some(quoted) => {
+            let (name, rest) = quoted.split_once('`')?;
+            (name, rest, true)
+        }
+        None => {
+            let end = line
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .unwrap_or(line.len());
+            (&line[..end], &line[end..], false)
+        }
+    };
+
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    let rest = rest.trim_start();
+
+    let description = ["-", ":", "–", "—"]
+        .iter()
+        .find_map(|sep| rest.strip_prefix(sep))
+        .map(str::trim_start);
+
+    match description {
+        Some(description) => Some((name, description)),
+        // A single word is only a parameter in a list, otherwise it continues a description
+        None if quoted || is_item && rest.is_empty() => Some((name, rest)),
+        None => None,
+    }
+}
+
+fn append(description: &mut String, line: &str) {
+    if !description.is_empty() {
+        description.push(' ');
+    }
+    description.push_str(line);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(doc: &StructuredDoc) -> Vec<(&str, &str)> {
+        doc.params
+            .iter()
+            .flatten()
+            .map(|(name, description)| (name.as_str(), description.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_tags() {
+        let doc = parse(
+            "Clamp a value.\n\n@param x the value\n  to clamp\n@param `lo` - lower bound\n@returns the result",
+        );
+
+        assert_eq!(doc.text.trim(), "Clamp a value.");
+        assert_eq!(
+            params(&doc),
+            [("x", "the value to clamp"), ("lo", "lower bound")]
+        );
+        assert_eq!(doc.returns.as_deref(), Some("the result"));
+    }
+
+    #[test]
+    fn parses_sections() {
+        let doc = parse(
+            "Add numbers.\n\n# Parameters\n\n* `a` - first\n  number\n* `b`: second\n\n## Returns\n\nThe sum.\n",
+        );
+
+        assert_eq!(doc.text.trim(), "Add numbers.");
+        assert_eq!(params(&doc), [("a", "first number"), ("b", "second")]);
+        assert_eq!(doc.returns.as_deref(), Some("The sum."));
+    }
+
+    #[test]
+    fn keeps_other_sections_and_text() {
+        let doc = parse("# Example\n\nSome text.\n\n# Parameters\n\nNone, really.");
+
+        assert_eq!(doc.text, "# Example\n\nSome text.\n\nNone, really.");
+        assert_eq!(doc.params, Some(Vec::new()));
+        assert_eq!(doc.returns, None);
+    }
+
+    #[test]
+    fn ignores_fenced_code() {
+        let doc = parse("Text.\n\n```rhai\n@param x not a tag\n# Parameters\n```");
+
+        assert_eq!(
+            doc.text,
+            "Text.\n\n```rhai\n@param x not a tag\n# Parameters\n```"
+        );
+        assert_eq!(doc.params, None);
+
+        let doc = parse("# Returns\n\nA map:\n\n```rhai\n#{ a: 1 }\n```");
+
+        assert_eq!(doc.text, "");
+        assert_eq!(
+            doc.returns.as_deref(),
+            Some("A map:\n\n```rhai\n#{ a: 1 }\n```")
+        );
+    }
+
+    #[test]
+    fn without_structured_docs() {
+        let doc = parse("Just text.\n\nMore text.");
+
+        assert_eq!(doc.text, "Just text.\n\nMore text.");
+        assert_eq!(doc.params, None);
+        assert_eq!(doc.returns, None);
+    }
+}
@@ -0,0 +1,258 @@
+use crate::{cli, load_config, watch};
+use rhai_doc::{error, write_log};
+use std::fs::File;
+use std::io::prelude::{BufRead, Read, Write};
+use std::io::BufReader;
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// URL polled by the live-reload snippet for the current build version.
+const VERSION_URL: &str = "/__rhai-doc/version";
+
+/// Get the URL path prefix from the `root` configuration option,
+/// which may be a path (e.g. `/docs/`) or a full URL (e.g. `https://example.com/docs/`).
+fn root_prefix(root: Option<&str>) -> String {
+    let root = root.unwrap_or_default();
+
+    let path = match root.find("://") {
+        Some(n) => root[n + 3..].find('/').map_or("", |p| &root[n + 3 + p..]),
+        None => root,
+    };
+
+    let path = path.trim_matches('/');
+
+    if path.is_empty() {
+        "/".to_string()
+    } else {
+        format!("/{}/", path)
+    }
+}
+
+/// Decode `%XX` escapes in a URL path.
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+
+            if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .as_deref()
+    {
+        Some("html") => "text/html; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("js") => "application/javascript; charset=utf-8",
+        Some("json") => "application/json",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("ico") => "image/x-icon",
+        Some("md") | Some("txt") => "text/plain; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
+
+fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    headers: &[(&str, &str)],
+    body: &[u8],
+) -> std::io::Result<()> {
+    write!(stream, "HTTP/1.1 {}\r\n", status)?;
+    for (name, value) in headers {
+        write!(stream, "{}: {}\r\n", name, value)?;
+    }
+    write!(
+        stream,
+        "Content-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    destination: &Path,
+    prefix: &str,
+    version: &AtomicUsize,
+    debug: bool,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Skip the headers
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+
+    write_log!(debug, "{} {}", method, target);
+
+    if method != "GET" && method != "HEAD" {
+        return respond(&mut stream, "405 Method Not Allowed", &[], b"");
+    }
+
+    let path = percent_decode(target.split(['?', '#']).next().unwrap_or_default());
+
+    if path == VERSION_URL {
+        let body = version.load(Ordering::SeqCst).to_string();
+        return respond(
+            &mut stream,
+            "200 OK",
+            &[("Content-Type", "text/plain")],
+            body.as_bytes(),
+        );
+    }
+
+    let relative = match path.strip_prefix(prefix) {
+        Some(relative) => relative,
+        None if path == "/" || path == prefix.trim_end_matches('/') => {
+            return respond(&mut stream, "302 Found", &[("Location", prefix)], b"");
+        }
+        None => return respond(&mut stream, "404 Not Found", &[], b"Not Found"),
+    };
+
+    let relative = PathBuf::from(relative);
+
+    // Never serve files outside the destination directory
+    if relative
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_)))
+    {
+        return respond(&mut stream, "403 Forbidden", &[], b"Forbidden");
+    }
+
+    let mut file_path = destination.join(&relative);
+
+    if file_path.is_dir() {
+        if !path.ends_with('/') {
+            let location = format!("{}/", path);
+            return respond(&mut stream, "302 Found", &[("Location", &location)], b"");
+        }
+        file_path.push("index.html");
+    }
+
+    let mut content = Vec::new();
+
+    match File::open(&file_path) {
+        Ok(mut file) => file.read_to_end(&mut content)?,
+        Err(_) => return respond(&mut stream, "404 Not Found", &[], b"Not Found"),
+    };
+
+    let content_type = content_type(&file_path);
+
+    if content_type.starts_with("text/html") {
+        let html = String::from_utf8_lossy(&content);
+        let snippet = std::str::from_utf8(include_bytes!("../assets/live-reload.html")).unwrap();
+
+        content = match html.rfind("</body>") {
+            Some(n) => format!("{}{}{}", &html[..n], snippet, &html[n..]),
+            None => format!("{}{}", html, snippet),
+        }
+        .into_bytes();
+    }
+
+    if method == "HEAD" {
+        content.clear();
+    }
+
+    respond(
+        &mut stream,
+        "200 OK",
+        &[("Content-Type", content_type)],
+        &content,
+    )
+}
+
+/// Generate the documentation and serve it over HTTP, regenerating it whenever a source file
+/// changes and reloading open pages afterwards.
+pub fn serve(
+    app: &cli::Cli,
+    host: &str,
+    port: u16,
+    delay: Duration,
+    quiet: bool,
+    debug: bool,
+) -> Result<(), error::RhaiDocError> {
+    let mut path_toml = app.directory.clone();
+    path_toml.push(&app.config);
+    let config = load_config(&path_toml)?;
+
+    let prefix = root_prefix(config.root.as_deref());
+    let mut destination = app.directory.clone();
+    destination.push(&app.destination);
+
+    let listener = match TcpListener::bind((host, port)) {
+        Ok(listener) => listener,
+        Err(error) => {
+            eprintln!(
+                "Cannot listen on `{host}:{port}`: {error}",
+                host = host,
+                port = port,
+                error = error
+            );
+            return Err(error.into());
+        }
+    };
+
+    println!(
+        "Serving documentation at http://{}:{}{}",
+        host, port, prefix
+    );
+
+    let version = Arc::new(AtomicUsize::new(0));
+
+    {
+        let version = version.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let destination = destination.clone();
+                let prefix = prefix.clone();
+                let version = version.clone();
+
+                std::thread::spawn(move || {
+                    if let Err(error) =
+                        handle_connection(stream, &destination, &prefix, &version, debug)
+                    {
+                        write_log!(debug, "Connection error: {}", error);
+                    }
+                });
+            }
+        });
+    }
+
+    watch::watch(app, delay, quiet, debug, |_| {
+        version.fetch_add(1, Ordering::SeqCst);
+    })
+}
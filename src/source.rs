@@ -0,0 +1,481 @@
+//! Helpers that work directly on the text of a Rhai script.
+
+use std::collections::HashMap;
+
+/// A top-level variable or constant declaration in a script.
+#[derive(Debug, Clone)]
+pub struct Declaration {
+    /// Name of the variable or constant.
+    pub name: String,
+    /// Source text of the initial value, if any, with whitespace collapsed.
+    pub value: Option<String>,
+    /// Is this a constant?
+    pub is_const: bool,
+    /// Is this declaration exported (via `export` or a later `export name;` statement)?
+    pub exported: bool,
+    /// Doc-comments in the same format as those of script-defined functions (e.g. `/// text`).
+    pub comments: Vec<String>,
+}
+
+/// Extract all top-level `let`/`const` declarations (optionally prefixed by `export`) from a
+/// script, together with the doc-comments immediately before them.
+///
+/// Rhai does not keep the doc-comments of variables in the AST, so the script text is scanned
+/// directly, skipping strings, comments and anything nested inside blocks.
+pub fn declarations(script: &str) -> Vec<Declaration> {
+    scan_declarations(script).0
+}
+
+/// Turn the doc-comments of top-level declarations into regular comments, so that the script
+/// compiles (Rhai only allows doc-comments on functions).
+///
+/// The shebang line, if any, is also blanked out, as `Engine::compile` (unlike
+/// `Engine::compile_file`) does not skip it.
+///
+/// Characters are only replaced, so positions in the script (e.g. of compile errors) are not
+/// changed.
+pub fn hide_declaration_comments(script: &str) -> String {
+    let mut bytes = script.as_bytes().to_vec();
+
+    let start = script.len() - script.trim_start_matches('\u{feff}').len();
+
+    if script[start..].starts_with("#!") {
+        let end = script[start..]
+            .find('\n')
+            .map_or(script.len(), |n| start + n);
+        bytes[start..end].fill(b' ');
+    }
+
+    for start in scan_declarations(script).1 {
+        // `///` becomes `// ` and `/**` becomes `/* `
+        bytes[start + 2] = b' ';
+    }
+
+    String::from_utf8(bytes).unwrap()
+}
+
+/// Line of the definition of each top-level function in a script, by name and number of
+/// parameters.
+///
+/// Function definitions have no position in the AST, so the script text is scanned directly.
+pub fn function_lines(script: &str) -> HashMap<(String, usize), usize> {
+    let mut scanner = Scanner::new(script);
+    let mut lines = HashMap::new();
+
+    while let Some(token) = scanner.next_token() {
+        let pos = match token {
+            Token::Word("fn") => scanner.pos - 2,
+            _ => continue,
+        };
+
+        let name = match scanner.next_token() {
+            Some(Token::Word(name)) => name,
+            _ => continue,
+        };
+
+        scanner.skip_trivia();
+
+        if scanner.peek() != Some('(') {
+            continue;
+        }
+
+        let start = scanner.pos;
+        scanner.skip_nested();
+        let params = scanner.text[start + 1..scanner.pos]
+            .trim_end_matches(')')
+            .split(',')
+            .filter(|param| !param.trim().is_empty())
+            .count();
+
+        let line = scanner.text[..pos].matches('\n').count() + 1;
+        lines.entry((name.to_string(), params)).or_insert(line);
+    }
+
+    lines
+}
+
+/// Scan the declarations of a script, also returning the starting byte positions of their
+/// doc-comments.
+fn scan_declarations(script: &str) -> (Vec<Declaration>, Vec<usize>) {
+    let mut scanner = Scanner::new(script);
+    let mut declarations: Vec<Declaration> = Vec::new();
+    let mut positions = Vec::new();
+    let mut comments = Vec::new();
+    let mut stmt_start = true;
+
+    while let Some(token) = scanner.next_token() {
+        match token {
+            Token::DocComment(text) => {
+                comments.push((text.as_ptr() as usize - script.as_ptr() as usize, text));
+                continue;
+            }
+            Token::Word("export") | Token::Word("let") | Token::Word("const") if stmt_start => {
+                let mut exported = false;
+                let mut word = token;
+
+                if word == Token::Word("export") {
+                    exported = true;
+                    word = scanner.next_token().unwrap_or(Token::Symbol(';'));
+                }
+
+                let is_const = match word {
+                    Token::Word("const") => true,
+                    Token::Word("let") => false,
+                    // `export name;` or `export name as alias;`
+                    Token::Word(name) if exported => {
+                        if let Some(decl) = declarations.iter_mut().rev().find(|d| d.name == name) {
+                            decl.exported = true;
+                        }
+                        scanner.skip_statement();
+                        comments.clear();
+                        stmt_start = true;
+                        continue;
+                    }
+                    _ => {
+                        scanner.skip_statement();
+                        comments.clear();
+                        stmt_start = true;
+                        continue;
+                    }
+                };
+
+                if let Some(Token::Word(name)) = scanner.next_token() {
+                    let value = match scanner.next_token() {
+                        Some(Token::Symbol('=')) => Some(scanner.skip_statement()),
+                        Some(Token::Symbol(';')) | None => None,
+                        _ => {
+                            scanner.skip_statement();
+                            None
+                        }
+                    };
+
+                    positions.extend(comments.iter().map(|&(pos, _)| pos));
+
+                    declarations.push(Declaration {
+                        name: name.to_string(),
+                        value: value.map(|v| v.split_whitespace().collect::<Vec<_>>().join(" ")),
+                        is_const,
+                        exported,
+                        comments: comments.iter().map(|(_, text)| text.to_string()).collect(),
+                    });
+                } else {
+                    scanner.skip_statement();
+                }
+
+                comments.clear();
+                stmt_start = true;
+                continue;
+            }
+            Token::Symbol(';') | Token::Block => stmt_start = true,
+            _ => stmt_start = false,
+        }
+
+        comments.clear();
+    }
+
+    (declarations, positions)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    Word(&'a str),
+    DocComment(&'a str),
+    Symbol(char),
+    /// A complete `{ ... }` block (e.g. a function body).
+    Block,
+    /// Strings, numbers and anything else.
+    Other,
+}
+
+/// A minimal scanner for the top level of a Rhai script.
+struct Scanner<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(text: &'a str) -> Self {
+        let mut scanner = Self {
+            text: text.trim_start_matches('\u{feff}'),
+            pos: 0,
+        };
+        if scanner.text.starts_with("#!") {
+            scanner.skip_while(|c| c != '\n');
+        }
+        scanner
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(&f) {
+            self.bump();
+        }
+        &self.text[start..self.pos]
+    }
+
+    /// Skip a block comment, which may be nested.
+    fn skip_block_comment(&mut self) -> &'a str {
+        let start = self.pos;
+        let mut level = 0;
+
+        while !self.rest().is_empty() {
+            if self.rest().starts_with("/*") {
+                level += 1;
+                self.pos += 2;
+            } else if self.rest().starts_with("*/") {
+                level -= 1;
+                self.pos += 2;
+                if level == 0 {
+                    break;
+                }
+            } else {
+                self.bump();
+            }
+        }
+        &self.text[start..self.pos]
+    }
+
+    /// Skip a string literal, which starts at the current position.
+    fn skip_string(&mut self, quote: char) {
+        self.bump();
+        while let Some(c) = self.bump() {
+            match c {
+                '\\' if quote != '`' => {
+                    self.bump();
+                }
+                c if c == quote => break,
+                _ => (),
+            }
+        }
+    }
+
+    /// Skip whitespace and regular comments.
+    fn skip_trivia(&mut self) {
+        loop {
+            self.skip_while(char::is_whitespace);
+
+            let rest = self.rest();
+
+            if rest.starts_with("//") && !rest.starts_with("///") && !rest.starts_with("//!")
+                || rest.starts_with("////")
+            {
+                self.skip_while(|c| c != '\n');
+            } else if rest.starts_with("/*") && !rest.starts_with("/**") || rest.starts_with("/**/")
+            {
+                self.skip_block_comment();
+            } else if rest.starts_with("//!") {
+                self.skip_while(|c| c != '\n');
+            } else if rest.starts_with("/*!") {
+                self.skip_block_comment();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Option<Token<'a>> {
+        self.skip_trivia();
+
+        let rest = self.rest();
+        let c = rest.chars().next()?;
+
+        if rest.starts_with("///") {
+            let text = self.skip_while(|c| c != '\n');
+            return Some(Token::DocComment(text.trim_end_matches('\r')));
+        }
+        if rest.starts_with("/**") {
+            return Some(Token::DocComment(self.skip_block_comment()));
+        }
+
+        let token = match c {
+            c if c.is_alphabetic() || c == '_' => {
+                Token::Word(self.skip_while(|c| c.is_alphanumeric() || c == '_'))
+            }
+            '"' | '\'' | '`' => {
+                self.skip_string(c);
+                Token::Other
+            }
+            c if c.is_ascii_digit() => {
+                self.skip_while(|c| c.is_alphanumeric() || c == '_' || c == '.');
+                Token::Other
+            }
+            '{' => {
+                self.skip_nested();
+                Token::Block
+            }
+            '(' | '[' => {
+                self.skip_nested();
+                Token::Other
+            }
+            c => {
+                self.bump();
+                Token::Symbol(c)
+            }
+        };
+
+        Some(token)
+    }
+
+    /// Skip a bracketed group (`{...}`, `(...)` or `[...]`) starting at the current position,
+    /// together with everything nested inside it.
+    fn skip_nested(&mut self) {
+        let mut level = 0;
+
+        loop {
+            self.skip_trivia();
+
+            match self.peek() {
+                None => break,
+                Some('{') | Some('(') | Some('[') => {
+                    level += 1;
+                    self.bump();
+                }
+                Some('}') | Some(')') | Some(']') => {
+                    level -= 1;
+                    self.bump();
+                    if level == 0 {
+                        break;
+                    }
+                }
+                Some(c @ '"') | Some(c @ '\'') | Some(c @ '`') => self.skip_string(c),
+                Some(_) => {
+                    self.bump();
+                }
+            }
+        }
+    }
+
+    /// Skip to the end of the current statement, returning its text (without the `;`).
+    fn skip_statement(&mut self) -> &'a str {
+        self.skip_trivia();
+        let start = self.pos;
+        let mut end = self.pos;
+
+        while let Some(token) = self.next_token() {
+            match token {
+                Token::Symbol(';') => break,
+                _ => end = self.pos,
+            }
+        }
+
+        &self.text[start..end]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(script: &str) -> Vec<String> {
+        declarations(script).into_iter().map(|d| d.name).collect()
+    }
+
+    #[test]
+    fn skips_declarations_in_strings_and_blocks() {
+        let script = r#"
+            let s = "let x = 1; const y = 2;";
+            let t = `const ${ "z" } = 3;`;
+            fn f() { let a = 1; const b = 2; }
+            if true { let c = 3; }
+            // const d = 4;
+            /* let e = 5; */
+            const A = [1, 2, 3];
+        "#;
+
+        assert_eq!(names(script), ["s", "t", "A"]);
+    }
+
+    #[test]
+    fn reads_values_and_doc_comments() {
+        let script = "/// The answer.\n/// Really.\nconst ANSWER = 40 +\n    2;\nlet x;";
+        let decls = declarations(script);
+
+        assert_eq!(decls.len(), 2);
+        assert_eq!(decls[0].name, "ANSWER");
+        assert_eq!(decls[0].value.as_deref(), Some("40 + 2"));
+        assert!(decls[0].is_const);
+        assert_eq!(decls[0].comments, ["/// The answer.", "/// Really."]);
+        assert_eq!(decls[1].name, "x");
+        assert_eq!(decls[1].value, None);
+        assert!(!decls[1].is_const);
+        assert!(decls[1].comments.is_empty());
+    }
+
+    #[test]
+    fn doc_comments_must_be_right_before_declaration() {
+        let script = "/// Not for x.\nprint(1);\nlet x = 1;";
+        assert!(declarations(script)[0].comments.is_empty());
+    }
+
+    #[test]
+    fn marks_exported_declarations() {
+        let script = "export const A = 1;\nlet b = 2;\nlet c = 3;\nexport c as alias;";
+        let decls = declarations(script);
+
+        let exported = decls
+            .iter()
+            .map(|d| (d.name.as_str(), d.exported))
+            .collect::<Vec<_>>();
+
+        assert_eq!(exported, [("A", true), ("b", false), ("c", true)]);
+    }
+
+    #[test]
+    fn hides_declaration_comments_in_place() {
+        let script = "/// Doc.\nconst A = 1;\n/** Block. */\nlet b = 2;\n/// Function.\nfn f() {}";
+        let hidden = hide_declaration_comments(script);
+
+        assert_eq!(hidden.len(), script.len());
+        assert_eq!(
+            hidden,
+            "//  Doc.\nconst A = 1;\n/*  Block. */\nlet b = 2;\n/// Function.\nfn f() {}"
+        );
+    }
+
+    #[test]
+    fn blanks_out_shebang_line() {
+        let script = "#!/usr/bin/env rhai\n/// Doc.\nconst A = 1;";
+
+        assert_eq!(
+            hide_declaration_comments(script),
+            "                   \n//  Doc.\nconst A = 1;"
+        );
+        assert!(rhai::Engine::new()
+            .compile(hide_declaration_comments(script))
+            .is_ok());
+        assert_eq!(hide_declaration_comments("#!rhai"), "      ");
+        assert_eq!(hide_declaration_comments("let x = 1; #!"), "let x = 1; #!");
+    }
+
+    #[test]
+    fn tolerates_unterminated_strings_and_comments() {
+        assert_eq!(names("let s = \"abc"), ["s"]);
+        assert!(names("/* let x = 1;").is_empty());
+        assert!(names("fn f() { let x = 1;").is_empty());
+    }
+
+    #[test]
+    fn finds_function_lines() {
+        let script = "\u{feff}/// Doc.\nfn f() {}\n\nprivate fn f(a, b) {\n    fn nested() {}\n}\nlet s = \"fn g() {}\";\n// fn h() {}\nfn k(\n    x,\n) {}";
+        let lines = function_lines(script);
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[&("f".to_string(), 0)], 2);
+        assert_eq!(lines[&("f".to_string(), 2)], 4);
+        assert_eq!(lines[&("k".to_string(), 1)], 9);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{build, cli};
+use notify_debouncer_mini::new_debouncer;
+use notify_debouncer_mini::notify::{self, RecursiveMode};
+use rhai_doc::{config, error, write_log, Summary};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+/// Files that trigger a rebuild when changed.
+struct WatchedFiles {
+    source: PathBuf,
+    pages: PathBuf,
+    destination: PathBuf,
+    config: PathBuf,
+}
+
+impl WatchedFiles {
+    fn new(app: &cli::Cli) -> Result<Self, error::RhaiDocError> {
+        let source = app.directory.canonicalize()?;
+        let absolute = |path: &Path| {
+            let path = source.join(path);
+            path.canonicalize().unwrap_or(path)
+        };
+
+        Ok(Self {
+            pages: absolute(&app.pages),
+            destination: absolute(&app.destination),
+            config: absolute(&app.config),
+            source,
+        })
+    }
+
+    /// Is a changed file relevant to the documentation?
+    ///
+    /// The configuration is re-loaded for each batch of changes because the script extension,
+    /// icon, stylesheet and templates directory may have changed.
+    fn is_relevant(&self, path: &Path, config: Option<&config::Config>) -> bool {
+        if path.starts_with(&self.destination) {
+            return false;
+        }
+        if path == self.config {
+            return true;
+        }
+
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        if path.starts_with(&self.pages) && extension == "md" {
+            return true;
+        }
+
+        let config = match config {
+            Some(config) => config,
+            None => return extension == "rhai",
+        };
+
+        let script_ext = config
+            .extension
+            .as_ref()
+            .map_or("rhai", |ext| ext.strip_prefix('.').unwrap_or(ext));
+
+        extension == script_ext
+            || [&config.icon, &config.stylesheet]
+                .iter()
+                .filter_map(|file| file.as_ref())
+                .chain(config.metadata.iter())
+                .any(|file| path == self.source.join(file))
+            || config
+                .templates
+                .as_ref()
+                .is_some_and(|dir| path.starts_with(self.source.join(dir)))
+    }
+}
+
+/// Generate the documentation, then regenerate it whenever a source file changes.
+///
+/// `on_build` is called after each successful build.
+/// Errors during regeneration are reported but do not stop watching.
+pub fn watch(
+    app: &cli::Cli,
+    delay: Duration,
+    quiet: bool,
+    debug: bool,
+    mut on_build: impl FnMut(&Summary),
+) -> Result<(), error::RhaiDocError> {
+    let files = WatchedFiles::new(app)?;
+
+    match build(app, quiet, debug) {
+        Ok(summary) => on_build(&summary),
+        Err(error) => eprintln!("Build failed: {}", error),
+    }
+
+    let (tx, rx) = channel();
+    let mut debouncer = new_debouncer(delay, tx).map_err(watch_error)?;
+
+    debouncer
+        .watcher()
+        .watch(&files.source, RecursiveMode::Recursive)
+        .map_err(watch_error)?;
+
+    if !files.pages.starts_with(&files.source) && files.pages.is_dir() {
+        debouncer
+            .watcher()
+            .watch(&files.pages, RecursiveMode::Recursive)
+            .map_err(watch_error)?;
+    }
+
+    write_log!(
+        !quiet,
+        "Watching `{}` for changes (press Ctrl-C to stop)...",
+        @files.source
+    );
+
+    for result in rx {
+        let events = match result {
+            Ok(events) => events,
+            Err(error) => {
+                eprintln!("Watch error: {}", error);
+                continue;
+            }
+        };
+
+        let config = config::Config::load(&files.config).ok();
+
+        let changed = events
+            .iter()
+            .filter(|event| files.is_relevant(&event.path, config.as_ref()))
+            .collect::<Vec<_>>();
+
+        if changed.is_empty() {
+            continue;
+        }
+
+        write_log!(!quiet, "");
+        for event in changed {
+            write_log!(!quiet, "Changed: `{}`", @event.path);
+        }
+
+        match build(app, quiet, debug) {
+            Ok(summary) => {
+                write_log!(
+                    !quiet,
+                    "Rebuilt {} page(s) and {} script(s) under `{}`",
+                    summary.pages,
+                    summary.scripts,
+                    summary.destination.to_string_lossy()
+                );
+                on_build(&summary);
+            }
+            Err(error) => eprintln!("Build failed: {}", error),
+        }
+    }
+
+    write_log!(!quiet, "Stopped watching.");
+
+    Ok(())
+}
+
+/// Convert a file watcher error, which the library does not know about.
+fn watch_error(error: notify::Error) -> error::RhaiDocError {
+    error::RhaiDocError::Internal(format!("Watch error: {}", error))
+}